@@ -1,12 +1,39 @@
-use crate::{auth, config::RuntimeConfig};
+use crate::{
+    clock,
+    config::{ensure_auth, RuntimeConfig},
+    error::KalshiError,
+    models::{
+        AmendOrderRequest, AmendedOrder, ApiKey, Balance, CanceledOrder, Candlestick,
+        CreateApiKeyRequest, CreateOrderRequest, CreatedApiKey, EventDetail, Market, Order,
+        OrderbookResponse, Page, Paginated, Series,
+    },
+    query::QueryParams,
+    ratelimit::Budget,
+    trace::{self, TraceRecord},
+};
+use futures_util::{stream, Stream, TryStreamExt};
 use reqwest::{Method, StatusCode};
-use serde_json::Value;
-use std::{collections::BTreeMap, time::Duration};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    time::{Duration, Instant},
+};
 
 const HTTP_TIMEOUT_SECS: u64 = 15;
 const MAX_RETRIES: u32 = 3;
 const MAX_BACKOFF_MS: u64 = 30_000;
 
+/// Page-size and item-cap controls for [`KalshiClient::paginate`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PageOpts {
+    /// Rows requested per page; `None` uses the endpoint maximum.
+    pub page_size: Option<usize>,
+    /// Stop after this many rows; `None` follows cursors to the end.
+    pub max_items: Option<usize>,
+}
+
 #[derive(Clone)]
 pub struct KalshiClient {
     http: reqwest::Client,
@@ -27,7 +54,12 @@ impl KalshiClient {
         path: &str,
         query: Option<BTreeMap<String, String>>,
     ) -> anyhow::Result<Value> {
-        let use_auth = self.runtime.api_key.is_some() && self.runtime.api_secret.is_some();
+        // Sign when we can, but never prompt for a passphrase just to read.
+        let use_auth = self
+            .runtime
+            .credentials
+            .as_ref()
+            .is_some_and(|c| c.loads_silently());
         self.request(Method::GET, path, query, None, use_auth).await
     }
 
@@ -47,6 +79,271 @@ impl KalshiClient {
         self.request(Method::DELETE, path, None, body, true).await
     }
 
+    pub async fn get_market(&self, ticker: &str) -> anyhow::Result<Market> {
+        let data = self.get_public(&format!("/markets/{ticker}"), None).await?;
+        take_field(data, "market")
+    }
+
+    pub async fn get_orderbook(
+        &self,
+        ticker: &str,
+        depth: Option<usize>,
+    ) -> anyhow::Result<OrderbookResponse> {
+        let q = QueryParams::new().optional("depth", depth).build();
+        let data = self
+            .get_public(&format!("/markets/{ticker}/orderbook"), q)
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    pub async fn get_event(&self, ticker: &str, with_markets: bool) -> anyhow::Result<EventDetail> {
+        let q = with_markets
+            .then(|| BTreeMap::from([("with_nested_markets".to_string(), "true".to_string())]));
+        let data = self.get_public(&format!("/events/{ticker}"), q).await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Candles of `period_minutes` (1, 60 or 1440) ending between `start_ts`
+    /// and `end_ts` (unix seconds).
+    pub async fn get_market_candlesticks(
+        &self,
+        series_ticker: &str,
+        ticker: &str,
+        start_ts: i64,
+        end_ts: i64,
+        period_minutes: u32,
+    ) -> anyhow::Result<Vec<Candlestick>> {
+        let q = QueryParams::new()
+            .insert("start_ts", start_ts)
+            .insert("end_ts", end_ts)
+            .insert("period_interval", period_minutes)
+            .build();
+        let data = self
+            .get_public(
+                &format!("/series/{series_ticker}/markets/{ticker}/candlesticks"),
+                q,
+            )
+            .await?;
+        take_field(data, "candlesticks")
+    }
+
+    pub async fn get_series(&self, ticker: &str) -> anyhow::Result<Series> {
+        let data = self.get_public(&format!("/series/{ticker}"), None).await?;
+        take_field(data, "series")
+    }
+
+    /// Every series, optionally narrowed by category and comma-separated
+    /// tags. The endpoint is not paginated.
+    pub async fn list_series(
+        &self,
+        category: Option<&str>,
+        tags: Option<&str>,
+    ) -> anyhow::Result<Vec<Series>> {
+        let q = QueryParams::new()
+            .optional("category", category)
+            .optional("tags", tags)
+            .build();
+        let data = self.get_public("/series", q).await?;
+        // Tolerate `"series": null` for filters that match nothing.
+        Ok(take_field::<Option<Vec<Series>>>(data, "series")?.unwrap_or_default())
+    }
+
+    pub async fn get_order(&self, order_id: &str) -> anyhow::Result<Order> {
+        let data = self
+            .get_auth(&format!("/portfolio/orders/{order_id}"), None)
+            .await?;
+        take_field(data, "order")
+    }
+
+    /// Submits an order exactly once. A `client_order_id` is attached when the
+    /// caller did not set one; if a submission fails ambiguously (timeout,
+    /// reset, 5xx) the order is looked up by that id before resubmitting, so a
+    /// retry can never rest or fill a second copy.
+    pub async fn create_order(&self, order: &CreateOrderRequest) -> anyhow::Result<Order> {
+        let mut order = order.clone();
+        let client_order_id = match &order.client_order_id {
+            Some(id) => id.clone(),
+            None => {
+                let id = new_client_order_id()?;
+                order.client_order_id = Some(id.clone());
+                id
+            }
+        };
+        let body = serde_json::to_value(&order)?;
+        let submitted_at = clock::now_secs();
+
+        submit_with_reconcile(
+            &order.ticker,
+            &client_order_id,
+            |attempt| {
+                let body = body.clone();
+                async move {
+                    if attempt > 0 {
+                        self.runtime.limiter.record_retry();
+                        tokio::time::sleep(backoff_delay(attempt, None)).await;
+                    }
+                    let data = self.post_auth("/portfolio/orders", Some(body)).await?;
+                    take_field(data, "order")
+                }
+            },
+            || self.find_order_by_client_id(&order.ticker, &client_order_id, submitted_at),
+        )
+        .await
+    }
+
+    async fn find_order_by_client_id(
+        &self,
+        ticker: &str,
+        client_order_id: &str,
+        submitted_at: i64,
+    ) -> anyhow::Result<Option<Order>> {
+        // Allow for clock drift between us and the exchange.
+        let q = QueryParams::new()
+            .insert("ticker", ticker)
+            .insert("min_ts", submitted_at - 300)
+            .build_always();
+        let orders: Vec<Order> = self.collect_pages(q, PageOpts::default()).await?;
+        Ok(orders
+            .into_iter()
+            .find(|o| o.client_order_id.as_deref() == Some(client_order_id)))
+    }
+
+    pub async fn amend_order(
+        &self,
+        order_id: &str,
+        amend: &AmendOrderRequest,
+    ) -> anyhow::Result<AmendedOrder> {
+        let data = self
+            .post_auth(
+                &format!("/portfolio/orders/{order_id}/amend"),
+                Some(serde_json::to_value(amend)?),
+            )
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    pub async fn cancel_order(&self, order_id: &str) -> anyhow::Result<CanceledOrder> {
+        let data = self
+            .delete_auth(&format!("/portfolio/orders/{order_id}"), None)
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    pub async fn batch_cancel_orders(&self, ids: &[String]) -> anyhow::Result<Vec<CanceledOrder>> {
+        let data = self
+            .delete_auth("/portfolio/orders/batched", Some(json!({ "ids": ids })))
+            .await?;
+        take_field(data, "orders")
+    }
+
+    pub async fn get_balance(&self) -> anyhow::Result<Balance> {
+        let data = self.get_auth("/portfolio/balance", None).await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    pub async fn list_api_keys(&self) -> anyhow::Result<Vec<ApiKey>> {
+        let data = self.get_auth("/api_keys", None).await?;
+        take_field(data, "api_keys")
+    }
+
+    pub async fn create_api_key(
+        &self,
+        request: &CreateApiKeyRequest,
+    ) -> anyhow::Result<CreatedApiKey> {
+        let data = self
+            .post_auth("/api_keys", Some(serde_json::to_value(request)?))
+            .await?;
+        Ok(serde_json::from_value(data)?)
+    }
+
+    pub async fn delete_api_key(&self, api_key_id: &str) -> anyhow::Result<()> {
+        self.delete_auth(&format!("/api_keys/{api_key_id}"), None)
+            .await?;
+        Ok(())
+    }
+
+    /// Offset of the exchange clock from the local clock in milliseconds
+    /// (positive when the server is ahead), read from the `Date` header of a
+    /// public request. The header has one-second resolution.
+    pub async fn clock_skew_ms(&self) -> anyhow::Result<i64> {
+        let (date, sent, received) = self.probe_date().await?;
+        clock::estimate_offset_ms(&date, sent, received)
+            .ok_or_else(|| anyhow::anyhow!("invalid Date header `{date}`"))
+    }
+
+    /// Learns the exchange clock offset before signing outside of REST
+    /// requests (the WebSocket handshake). No-op once any response was seen.
+    pub async fn sync_clock(&self) -> anyhow::Result<()> {
+        if self.runtime.clock.is_synced() {
+            return Ok(());
+        }
+        let (date, sent, received) = self.probe_date().await?;
+        self.runtime.clock.observe(&date, sent, received);
+        Ok(())
+    }
+
+    async fn probe_date(&self) -> anyhow::Result<(String, i64, i64)> {
+        self.runtime.limiter.acquire(Budget::Read).await;
+        let url = format!("{}/exchange/status", self.runtime.rest_base_url());
+
+        let sent = clock::local_now_ms();
+        let response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(KalshiError::Network)?;
+        let received = clock::local_now_ms();
+
+        let date = response_date(&response)
+            .ok_or_else(|| anyhow::anyhow!("response has no Date header"))?;
+        Ok((date.to_string(), sent, received))
+    }
+
+    /// Streams rows of a cursor-paginated endpoint, fetching further pages
+    /// lazily until the cursor runs out or `opts.max_items` rows were yielded.
+    /// `limit` and `cursor` in `query` are managed by the stream.
+    pub fn paginate<T: Paginated>(
+        &self,
+        query: BTreeMap<String, String>,
+        opts: PageOpts,
+    ) -> impl Stream<Item = anyhow::Result<T>> + '_ {
+        paginate_with(query, opts, T::MAX_PAGE_SIZE, move |q| {
+            self.get_page::<T>(Some(q))
+        })
+    }
+
+    /// Collects [`KalshiClient::paginate`] into a `Vec`.
+    pub async fn collect_pages<T: Paginated>(
+        &self,
+        query: BTreeMap<String, String>,
+        opts: PageOpts,
+    ) -> anyhow::Result<Vec<T>> {
+        self.paginate(query, opts).try_collect().await
+    }
+
+    async fn get_page<T: Paginated>(
+        &self,
+        query: Option<BTreeMap<String, String>>,
+    ) -> anyhow::Result<Page<T>> {
+        let mut data = if T::AUTH {
+            self.get_auth(T::PATH, query).await?
+        } else {
+            self.get_public(T::PATH, query).await?
+        };
+
+        let cursor = data
+            .get("cursor")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let items = match data.get_mut(T::KEY).map(Value::take) {
+            Some(Value::Null) | None => Vec::new(),
+            Some(raw) => serde_json::from_value(raw)?,
+        };
+
+        Ok(Page { items, cursor })
+    }
+
     async fn request(
         &self,
         method: Method,
@@ -58,9 +355,17 @@ impl KalshiClient {
         let base_url = self.runtime.rest_base_url();
         let url = format!("{}{}", base_url, path);
 
+        let budget = if method == Method::GET {
+            Budget::Read
+        } else {
+            Budget::Write
+        };
         let mut attempt = 0_u32;
+        let mut resigned = false;
 
         loop {
+            self.runtime.limiter.acquire(budget).await;
+
             let mut req = self.http.request(method.clone(), &url);
 
             if let Some(q) = &query {
@@ -72,68 +377,232 @@ impl KalshiClient {
             }
 
             if auth_required {
-                let api_key = self
-                    .runtime
-                    .api_key
-                    .as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("missing api key"))?;
-                let api_secret = self
-                    .runtime
-                    .api_secret
-                    .as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("missing api secret"))?;
-
+                let credentials = ensure_auth(&self.runtime)?;
                 let signed_path = signed_path(path, &query);
-                for (name, value) in auth::get_auth_headers(
-                    api_key,
-                    &auth::parse_private_key(api_secret),
+                for (name, value) in credentials.auth_headers(
+                    self.runtime.clock.now_ms(),
                     method.as_str(),
-                    &format!("/trade-api/v2{}", signed_path),
+                    &format!("{}{}", self.runtime.rest_sign_prefix(), signed_path),
                 )? {
                     req = req.header(name, value);
                 }
             }
 
-            let response = req.send().await?;
-            if response.status().is_success() {
-                if response.status() == StatusCode::NO_CONTENT {
+            // Reads are idempotent and safe to replay after any transient failure.
+            // Writes are only replayed on 429, where Kalshi rejected the request
+            // before processing it; other failures are ambiguous and surface to
+            // the caller (see `create_order`).
+            let idempotent = budget == Budget::Read;
+
+            let request = req.build().map_err(KalshiError::Network)?;
+            let trace = self
+                .runtime
+                .tracer
+                .enabled()
+                .then(|| self.start_trace(&request, &query, &body, attempt + 1));
+            let started = Instant::now();
+            let sent_ms = clock::local_now_ms();
+
+            let response = match self.http.execute(request).await {
+                Ok(response) => response,
+                Err(err) => {
+                    self.finish_trace(trace, started, None, None, Some(&err));
+                    if idempotent && attempt < MAX_RETRIES {
+                        attempt += 1;
+                        self.runtime.limiter.record_retry();
+                        tokio::time::sleep(backoff_delay(attempt, None)).await;
+                        continue;
+                    }
+                    return Err(KalshiError::Network(err).into());
+                }
+            };
+
+            let status = response.status();
+            let resynced = response_date(&response).is_some_and(|date| {
+                self.runtime
+                    .clock
+                    .observe(date, sent_ms, clock::local_now_ms())
+            });
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let text = match response.text().await {
+                Ok(text) => text,
+                Err(err) => {
+                    self.finish_trace(trace, started, Some(status), None, Some(&err));
+                    return Err(KalshiError::Network(err).into());
+                }
+            };
+            self.finish_trace(trace, started, Some(status), Some(&text), None);
+
+            if status.is_success() {
+                if status == StatusCode::NO_CONTENT || text.trim().is_empty() {
                     return Ok(serde_json::json!({}));
                 }
-                return Ok(response.json::<Value>().await?);
+                return serde_json::from_str::<Value>(&text)
+                    .map_err(|err| anyhow::anyhow!("invalid JSON from kalshi api: {err}"));
             }
 
-            if matches!(
-                response.status(),
-                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
-            ) && attempt < MAX_RETRIES
+            // A signature rejected for its timestamp is re-signed once, now
+            // with the offset learned from this response's `Date` header.
+            if status == StatusCode::UNAUTHORIZED
+                && auth_required
+                && !resigned
+                && (resynced || is_timestamp_rejection(&text))
             {
+                resigned = true;
                 attempt += 1;
-                let retry_after_ms = response
-                    .headers()
-                    .get(reqwest::header::RETRY_AFTER)
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .map(|secs| secs.saturating_mul(1000));
-                let exponential_ms = (1000_u64 * (1_u64 << (attempt - 1))).min(MAX_BACKOFF_MS);
-                let jitter_seed = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.subsec_nanos() as u64)
-                    .unwrap_or(500_000_000);
-                let jitter_factor = 0.5 + (jitter_seed % 1000) as f64 / 1000.0; // 0.5..1.499
-                let jitter_ms = (exponential_ms as f64 * jitter_factor).round() as u64;
-                let backoff_ms = retry_after_ms.unwrap_or(jitter_ms.min(MAX_BACKOFF_MS));
-                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                self.runtime.limiter.record_retry();
                 continue;
             }
 
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::from("<empty>"));
-            anyhow::bail!("kalshi api error {}: {}", status, text);
+            let retryable =
+                status == StatusCode::TOO_MANY_REQUESTS || (idempotent && status.is_server_error());
+            if retryable && attempt < MAX_RETRIES {
+                attempt += 1;
+                self.runtime.limiter.record_retry();
+                tokio::time::sleep(backoff_delay(attempt, retry_after)).await;
+                continue;
+            }
+
+            let text = if text.is_empty() {
+                String::from("<empty>")
+            } else {
+                text
+            };
+            return Err(KalshiError::from_response(status, &text).into());
+        }
+    }
+
+    fn start_trace(
+        &self,
+        request: &reqwest::Request,
+        query: &Option<BTreeMap<String, String>>,
+        body: &Option<Value>,
+        attempt: u32,
+    ) -> TraceRecord {
+        let wants_bodies = self.runtime.tracer.wants_bodies();
+        TraceRecord {
+            ts_ms: clock::local_now_ms(),
+            attempt,
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            query: query.as_ref().map(|q| json!(q)),
+            request_headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    let value = value.to_str().unwrap_or("<binary>");
+                    (name.to_string(), trace::redact_header(name.as_str(), value))
+                })
+                .collect(),
+            request_body: body.clone().filter(|_| wants_bodies),
+            status: None,
+            latency_ms: 0,
+            response_body: None,
+            error: None,
         }
     }
+
+    fn finish_trace(
+        &self,
+        trace: Option<TraceRecord>,
+        started: Instant,
+        status: Option<StatusCode>,
+        response_body: Option<&str>,
+        error: Option<&dyn std::fmt::Display>,
+    ) {
+        let Some(mut record) = trace else {
+            return;
+        };
+        record.latency_ms = started.elapsed().as_millis();
+        record.status = status.map(|s| s.as_u16());
+        if self.runtime.tracer.wants_bodies() {
+            record.response_body = response_body.map(str::to_string);
+        }
+        record.error = error.map(|e| e.to_string());
+        self.runtime.tracer.record(&record);
+    }
+}
+
+/// Exponential backoff with jitter, capped at `MAX_BACKOFF_MS`. A server
+/// supplied `Retry-After` wins, within the same cap.
+fn backoff_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    if let Some(delay) = retry_after {
+        return delay.min(Duration::from_millis(MAX_BACKOFF_MS));
+    }
+    let exponential_ms = (1000_u64 * (1_u64 << (attempt.max(1) - 1))).min(MAX_BACKOFF_MS);
+    let jitter_seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(500_000_000);
+    let jitter_factor = 0.5 + (jitter_seed % 1000) as f64 / 1000.0; // 0.5..1.499
+    let jitter_ms = (exponential_ms as f64 * jitter_factor).round() as u64;
+    Duration::from_millis(jitter_ms.min(MAX_BACKOFF_MS))
+}
+
+/// True when a write may or may not have been applied: the connection failed
+/// mid-flight or the server errored after accepting the request.
+fn is_ambiguous_write_failure(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<KalshiError>() {
+        Some(KalshiError::Network(_)) => true,
+        Some(KalshiError::Api(api)) => api.status >= 500,
+        _ => false,
+    }
+}
+
+/// API error codes for an order whose `client_order_id` was already used.
+const DUPLICATE_ORDER_CODES: &[&str] = &["order_already_exists", "duplicate_client_order_id"];
+
+fn is_duplicate_client_order_id(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<KalshiError>()
+        .and_then(KalshiError::api_error)
+        .and_then(|api| api.code.as_deref())
+        .is_some_and(|code| DUPLICATE_ORDER_CODES.contains(&code))
+}
+
+/// Random RFC 4122 v4 UUID used as `client_order_id`.
+fn new_client_order_id() -> anyhow::Result<String> {
+    let mut bytes = [0_u8; 16];
+    openssl::rand::rand_bytes(&mut bytes)?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+fn response_date(response: &reqwest::Response) -> Option<&str> {
+    response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|v| v.to_str().ok())
+}
+
+/// Whether a 401 body blames the request timestamp rather than the key.
+fn is_timestamp_rejection(text: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    ["timestamp", "clock", "expired"]
+        .iter()
+        .any(|needle| text.contains(needle))
+}
+
+/// Pulls `key` out of a response envelope such as `{"market": {...}}`.
+fn take_field<T: DeserializeOwned>(mut data: Value, key: &str) -> anyhow::Result<T> {
+    let raw = data
+        .get_mut(key)
+        .map(Value::take)
+        .ok_or_else(|| anyhow::anyhow!("kalshi response missing `{key}`"))?;
+    Ok(serde_json::from_value(raw)?)
 }
 
 fn signed_path(path: &str, query: &Option<BTreeMap<String, String>>) -> String {
@@ -151,3 +620,273 @@ fn signed_path(path: &str, query: &Option<BTreeMap<String, String>>) -> String {
     }
     out
 }
+
+/// The submit/reconcile loop behind [`KalshiClient::create_order`]. `submit`
+/// receives the attempt number (0 first) and backs off itself on retries;
+/// `lookup` searches for an order already resting under `client_order_id`.
+async fn submit_with_reconcile<T, S, SubmitFut, L, LookupFut>(
+    ticker: &str,
+    client_order_id: &str,
+    mut submit: S,
+    mut lookup: L,
+) -> anyhow::Result<T>
+where
+    S: FnMut(u32) -> SubmitFut,
+    SubmitFut: Future<Output = anyhow::Result<T>>,
+    L: FnMut() -> LookupFut,
+    LookupFut: Future<Output = anyhow::Result<Option<T>>>,
+{
+    let outcome_unknown = || {
+        format!(
+            "order submission outcome unknown; check `kal order list --ticker {ticker}` for client_order_id {client_order_id}"
+        )
+    };
+    let mut attempt = 0_u32;
+
+    loop {
+        let err = match submit(attempt).await {
+            Ok(order) => return Ok(order),
+            Err(err) => err,
+        };
+
+        let ambiguous = is_ambiguous_write_failure(&err);
+        // A duplicate id on a resubmission means an earlier attempt landed.
+        let duplicate = attempt > 0 && is_duplicate_client_order_id(&err);
+        if !ambiguous && !duplicate {
+            return Err(err);
+        }
+
+        // A failed lookup leaves the outcome just as unknown as the submit.
+        let found = lookup().await.map_err(|e| e.context(outcome_unknown()))?;
+        if let Some(existing) = found {
+            return Ok(existing);
+        }
+        if duplicate || attempt >= MAX_RETRIES {
+            return Err(err.context(outcome_unknown()));
+        }
+        attempt += 1;
+    }
+}
+
+/// The cursor loop behind [`KalshiClient::paginate`], over any page source.
+fn paginate_with<'a, T, F, Fut>(
+    query: BTreeMap<String, String>,
+    opts: PageOpts,
+    max_page_size: usize,
+    fetch_page: F,
+) -> impl Stream<Item = anyhow::Result<T>> + 'a
+where
+    T: 'a,
+    F: Fn(BTreeMap<String, String>) -> Fut + 'a,
+    Fut: Future<Output = anyhow::Result<Page<T>>> + 'a,
+{
+    let page_size = opts
+        .page_size
+        .unwrap_or(max_page_size)
+        .clamp(1, max_page_size);
+
+    stream::try_unfold(
+        (None::<String>, 0_usize, false),
+        move |(cursor, fetched, done)| {
+            let remaining = opts.max_items.map(|max| max.saturating_sub(fetched));
+            let request = (!done && remaining != Some(0)).then(|| {
+                let mut q = query.clone();
+                let limit = remaining.map_or(page_size, |r| r.min(page_size));
+                q.insert("limit".to_string(), limit.to_string());
+                match cursor {
+                    Some(c) => q.insert("cursor".to_string(), c),
+                    None => q.remove("cursor"),
+                };
+                fetch_page(q)
+            });
+
+            async move {
+                let Some(request) = request else {
+                    return Ok(None);
+                };
+                let mut page = request.await?;
+                if let Some(r) = remaining {
+                    page.items.truncate(r);
+                }
+                let fetched = fetched + page.items.len();
+                // An empty cursor, like a missing one, marks the last page.
+                let cursor = page.cursor.filter(|c| !c.is_empty());
+                let done = page.items.is_empty() || cursor.is_none();
+                Ok::<_, anyhow::Error>(Some((page.items, (cursor, fetched, done))))
+            }
+        },
+    )
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::BTreeMap, time::Duration};
+
+    use futures_util::TryStreamExt;
+    use reqwest::StatusCode;
+
+    use super::{
+        backoff_delay, is_ambiguous_write_failure, is_duplicate_client_order_id,
+        new_client_order_id, paginate_with, submit_with_reconcile, PageOpts, MAX_BACKOFF_MS,
+    };
+    use crate::{error::KalshiError, models::Page};
+
+    #[tokio::test]
+    async fn reconciles_ambiguous_order_submissions() {
+        let timeout = || {
+            anyhow::Error::new(KalshiError::from_response(
+                StatusCode::GATEWAY_TIMEOUT,
+                "upstream timeout",
+            ))
+        };
+        let submits = RefCell::new(0);
+        let submit = |attempt: u32| {
+            *submits.borrow_mut() += 1;
+            async move {
+                assert_eq!(attempt, 0, "must not resubmit once the order is found");
+                Err::<&str, _>(timeout())
+            }
+        };
+
+        // Ambiguous failure, lookup finds the order: return it, no resubmit.
+        let found = submit_with_reconcile("KX", "cid", submit, || async { Ok(Some("existing")) })
+            .await
+            .unwrap();
+        assert_eq!(found, "existing");
+        assert_eq!(*submits.borrow(), 1);
+
+        // A failed lookup surfaces as an unknown outcome, not a bare error.
+        let err = submit_with_reconcile(
+            "KX",
+            "cid",
+            |_| async { Err::<&str, _>(timeout()) },
+            || async { Err(timeout()) },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("outcome unknown"), "{err}");
+        assert!(err.to_string().contains("client_order_id cid"), "{err}");
+
+        // Nothing found: resubmit, and accept the retry's result.
+        let attempts = RefCell::new(Vec::new());
+        let order = submit_with_reconcile(
+            "KX",
+            "cid",
+            |attempt| {
+                attempts.borrow_mut().push(attempt);
+                async move {
+                    if attempt == 0 {
+                        Err(timeout())
+                    } else {
+                        Ok("placed")
+                    }
+                }
+            },
+            || async { Ok(None) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(order, "placed");
+        assert_eq!(*attempts.borrow(), [0, 1]);
+    }
+
+    #[tokio::test]
+    async fn paginates_until_cursor_or_max_items() {
+        // Serves 1..=7 in pages of up to 3 regardless of `limit`, like an
+        // endpoint ignoring it; the last page carries an empty cursor.
+        let requests = RefCell::new(Vec::new());
+        let source = |q: BTreeMap<String, String>| {
+            requests
+                .borrow_mut()
+                .push((q["limit"].clone(), q.get("cursor").cloned()));
+            let (items, cursor) = match q.get("cursor").map(String::as_str) {
+                None => (vec![1, 2, 3], "c1"),
+                Some("c1") => (vec![4, 5, 6], "c2"),
+                _ => (vec![7], ""),
+            };
+            let cursor = Some(cursor.to_string());
+            async move { Ok(Page { items, cursor }) }
+        };
+        let collect = |opts: PageOpts| {
+            requests.borrow_mut().clear();
+            paginate_with(BTreeMap::new(), opts, 3, &source).try_collect::<Vec<i32>>()
+        };
+
+        let all = collect(PageOpts {
+            page_size: Some(50),
+            max_items: None,
+        })
+        .await
+        .unwrap();
+        assert_eq!(all, [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(
+            *requests.borrow(),
+            [
+                ("3".to_string(), None),
+                ("3".to_string(), Some("c1".to_string())),
+                ("3".to_string(), Some("c2".to_string())),
+            ]
+        );
+
+        let first = collect(PageOpts {
+            page_size: None,
+            max_items: Some(4),
+        })
+        .await
+        .unwrap();
+        assert_eq!(first, [1, 2, 3, 4]);
+        assert_eq!(
+            *requests.borrow(),
+            [
+                ("3".to_string(), None),
+                ("1".to_string(), Some("c1".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn client_order_ids_are_v4_uuids() {
+        let id = new_client_order_id().unwrap();
+        assert_eq!(id.len(), 36);
+        assert_eq!(id.as_bytes()[14], b'4');
+        assert_ne!(id, new_client_order_id().unwrap());
+    }
+
+    #[test]
+    fn caps_server_supplied_retry_after() {
+        let cap = Duration::from_millis(MAX_BACKOFF_MS);
+        let short = Duration::from_secs(2);
+        assert_eq!(backoff_delay(1, Some(short)), short);
+        assert_eq!(backoff_delay(1, Some(Duration::from_secs(86_400))), cap);
+        assert!(backoff_delay(10, None) <= cap);
+    }
+
+    #[test]
+    fn only_server_errors_are_ambiguous() {
+        let server = KalshiError::from_response(StatusCode::BAD_GATEWAY, "").into();
+        let rejected = KalshiError::from_response(StatusCode::BAD_REQUEST, "").into();
+        assert!(is_ambiguous_write_failure(&server));
+        assert!(!is_ambiguous_write_failure(&rejected));
+
+        let duplicate = KalshiError::from_response(
+            StatusCode::CONFLICT,
+            r#"{"error":{"code":"order_already_exists","message":"duplicate"}}"#,
+        )
+        .into();
+        assert!(is_duplicate_client_order_id(&duplicate));
+        let rejected = KalshiError::from_response(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"code":"invalid_parameters","message":"client_order_id already exists or is a duplicate"}}"#,
+        )
+        .into();
+        assert!(!is_duplicate_client_order_id(&rejected));
+        let similar = KalshiError::from_response(
+            StatusCode::CONFLICT,
+            r#"{"error":{"code":"order_already_exists_elsewhere"}}"#,
+        )
+        .into();
+        assert!(!is_duplicate_client_order_id(&similar));
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicI64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Offsets smaller than this are within the `Date` header's one-second
+/// resolution plus network latency, and are not applied.
+const MIN_CORRECTION_MS: i64 = 1_000;
+/// Skew at or beyond this is reported once on stderr.
+const WARN_SKEW_MS: i64 = 2_000;
+
+/// Estimated offset of the exchange clock from the local clock, learned from
+/// response `Date` headers and added to `KALSHI-ACCESS-TIMESTAMP`. Shared by
+/// every client built from the same `RuntimeConfig`.
+#[derive(Debug, Default)]
+pub struct ClockSync {
+    offset_ms: AtomicI64,
+    synced: AtomicBool,
+    warned: AtomicBool,
+}
+
+impl ClockSync {
+    /// Current time on the exchange clock, as far as we know it.
+    pub fn now_ms(&self) -> i64 {
+        local_now_ms() + self.offset_ms()
+    }
+
+    pub fn offset_ms(&self) -> i64 {
+        self.offset_ms.load(Ordering::Relaxed)
+    }
+
+    /// Whether any server `Date` header has been observed yet.
+    pub fn is_synced(&self) -> bool {
+        self.synced.load(Ordering::Relaxed)
+    }
+
+    /// Folds in the `Date` header of a response to a request sent at
+    /// `sent_ms` and received at `received_ms` (local clock). Returns `true`
+    /// when the applied offset changed.
+    pub fn observe(&self, date: &str, sent_ms: i64, received_ms: i64) -> bool {
+        let Some(estimate) = estimate_offset_ms(date, sent_ms, received_ms) else {
+            return false;
+        };
+        self.synced.store(true, Ordering::Relaxed);
+
+        let target = if estimate.abs() < MIN_CORRECTION_MS {
+            0
+        } else {
+            estimate
+        };
+        if (target - self.offset_ms()).abs() < MIN_CORRECTION_MS {
+            return false;
+        }
+        self.offset_ms.store(target, Ordering::Relaxed);
+
+        if target.abs() >= WARN_SKEW_MS && !self.warned.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "warning: local clock is {:.1}s {} the exchange; compensating when signing requests",
+                target.abs() as f64 / 1000.0,
+                if target > 0 { "behind" } else { "ahead of" }
+            );
+        }
+        true
+    }
+}
+
+/// Offset of the server clock from the local clock in milliseconds (positive
+/// when the server is ahead), from an HTTP `Date` header.
+pub fn estimate_offset_ms(date: &str, sent_ms: i64, received_ms: i64) -> Option<i64> {
+    let server_ms = httpdate::parse_http_date(date)
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as i64;
+    // The header truncates to whole seconds; compare the middle of that
+    // second with the middle of the round trip.
+    Some(server_ms + 500 - (sent_ms + received_ms) / 2)
+}
+
+pub fn local_now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Unix seconds on the local clock.
+pub fn now_secs() -> i64 {
+    local_now_ms() / 1_000
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::ClockSync;
+
+    fn http_date(ms: i64) -> String {
+        httpdate::fmt_http_date(UNIX_EPOCH + Duration::from_millis(ms as u64))
+    }
+
+    #[test]
+    fn applies_only_significant_skew() {
+        let clock = ClockSync::default();
+        let local = 1_700_000_000_000;
+
+        // Same second as the local clock: nothing to correct.
+        assert!(!clock.observe(&http_date(local), local, local));
+        assert!(clock.is_synced());
+        assert_eq!(clock.offset_ms(), 0);
+
+        // Server 30s ahead.
+        assert!(clock.observe(&http_date(local + 30_000), local, local));
+        assert!((clock.offset_ms() - 30_000).abs() <= 1_000);
+
+        // Jitter within the header resolution keeps the current offset.
+        assert!(!clock.observe(&http_date(local + 30_400), local, local));
+
+        // Clock fixed again.
+        assert!(clock.observe(&http_date(local), local, local));
+        assert_eq!(clock.offset_ms(), 0);
+    }
+}
@@ -0,0 +1,173 @@
+use std::{fs, io::IsTerminal, path::PathBuf};
+
+use clap::{Args, Subcommand};
+use dialoguer::Confirm;
+use serde_json::json;
+
+use crate::{
+    auth::generate_key_pair,
+    client::KalshiClient,
+    config::{create_private_file, ensure_auth},
+    error::KalshiError,
+    models::CreateApiKeyRequest,
+    output::{print_rows, print_value},
+    AppContext,
+};
+
+#[derive(Debug, Clone, Args)]
+pub struct ApiKeysCmd {
+    #[command(subcommand)]
+    command: ApiKeysSubcmd,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum ApiKeysSubcmd {
+    /// List API keys on the account
+    List,
+    /// Register a new API key for an RSA public key
+    Create {
+        #[arg(long)]
+        name: String,
+        /// Public key PEM to upload (e.g. from `kal config keygen`)
+        #[arg(
+            long,
+            value_name = "PATH",
+            required_unless_present = "generate",
+            conflicts_with = "generate"
+        )]
+        public_key: Option<PathBuf>,
+        /// Generate a key pair locally, writing the private key to PATH (0600)
+        #[arg(long, value_name = "PATH")]
+        generate: Option<PathBuf>,
+        /// RSA modulus size for --generate: 2048 or 4096
+        #[arg(long, default_value_t = 2048, requires = "generate")]
+        bits: u32,
+        /// Comma-separated permission scopes (default: the exchange default)
+        #[arg(long, value_delimiter = ',')]
+        scopes: Vec<String>,
+    },
+    /// Revoke an API key
+    Delete {
+        api_key_id: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+pub async fn run(ctx: &AppContext, cmd: ApiKeysCmd) -> anyhow::Result<()> {
+    let credentials = ensure_auth(&ctx.runtime)?;
+    let client = KalshiClient::new(ctx.runtime.clone())?;
+
+    match cmd.command {
+        ApiKeysSubcmd::List => {
+            let keys = client.list_api_keys().await?;
+            print_rows(
+                ctx.output_mode,
+                &ctx.display,
+                &keys,
+                &["api_key_id", "name", "scopes"],
+            )
+        }
+        ApiKeysSubcmd::Create {
+            name,
+            public_key,
+            generate,
+            bits,
+            scopes,
+        } => {
+            let (public_key, private_key_path) = match (public_key, generate) {
+                (Some(path), _) => {
+                    let pem = fs::read_to_string(&path).map_err(|err| {
+                        KalshiError::config(format!("cannot read {}: {err}", path.display()))
+                    })?;
+                    if !pem.contains("-----BEGIN PUBLIC KEY-----") {
+                        return Err(KalshiError::config(format!(
+                            "{} is not a PEM public key (`BEGIN PUBLIC KEY`)",
+                            path.display()
+                        ))
+                        .into());
+                    }
+                    (pem, None)
+                }
+                (None, Some(path)) => {
+                    if path.exists() {
+                        return Err(KalshiError::config(format!(
+                            "{} already exists",
+                            path.display()
+                        ))
+                        .into());
+                    }
+                    let (private_pem, public_pem) = generate_key_pair(bits)
+                        .map_err(|err| KalshiError::config(err.to_string()))?;
+                    // Written before the key is registered so an accepted key
+                    // always has its private half on disk; removed below if
+                    // the API rejects it.
+                    create_private_file(&path, &private_pem).map_err(|err| {
+                        KalshiError::config(format!("cannot write {}: {err}", path.display()))
+                    })?;
+                    (public_pem, Some(path))
+                }
+                (None, None) => unreachable!("clap requires --public-key or --generate"),
+            };
+
+            let request = CreateApiKeyRequest {
+                name: name.clone(),
+                public_key,
+                scopes: (!scopes.is_empty()).then_some(scopes),
+            };
+            let created = match client.create_api_key(&request).await {
+                Ok(created) => created,
+                Err(err) => {
+                    if let Some(path) = &private_key_path {
+                        let _ = fs::remove_file(path);
+                    }
+                    return Err(err);
+                }
+            };
+            print_value(
+                ctx.output_mode,
+                &ctx.display,
+                &json!({
+                    "api_key_id": created.api_key_id,
+                    "name": name,
+                    "private_key_path": private_key_path.map(|p| p.display().to_string()),
+                }),
+            )
+        }
+        ApiKeysSubcmd::Delete { api_key_id, yes } => {
+            let in_use = credentials.api_key() == api_key_id;
+            if !yes {
+                if !std::io::stdin().is_terminal() {
+                    return Err(KalshiError::config(
+                        "refusing to delete an API key without confirmation; pass --yes",
+                    )
+                    .into());
+                }
+                let prompt = if in_use {
+                    format!("Delete API key {api_key_id}? This is the key kal is using now")
+                } else {
+                    format!("Delete API key {api_key_id}?")
+                };
+                if !Confirm::new()
+                    .with_prompt(prompt)
+                    .default(false)
+                    .interact()?
+                {
+                    return print_value(
+                        ctx.output_mode,
+                        &ctx.display,
+                        &json!({"deleted": false, "api_key_id": api_key_id}),
+                    );
+                }
+            }
+
+            client.delete_api_key(&api_key_id).await?;
+            print_value(
+                ctx.output_mode,
+                &ctx.display,
+                &json!({"deleted": true, "api_key_id": api_key_id, "was_active_key": in_use}),
+            )
+        }
+    }
+}
@@ -1,12 +1,32 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
 use clap::{Args, Subcommand};
-use dialoguer::{Confirm, Input, Select};
-use serde_json::json;
+use dialoguer::{Confirm, Input, Password, Select};
+use serde::Serialize;
+use serde_json::{json, Value};
 
 use crate::{
+    auth::{encrypt_private_key, generate_key_pair, is_encrypted_key},
+    client::KalshiClient,
     config::{
-        config_path, delete_config, load_stored_config, save_config, Environment, StoredConfig,
+        canonical_key, config_path, create_private_file, default_rest_base_url, delete_config,
+        ensure_auth, env_name, load_config_file, resolve_settings, save_config_file,
+        write_private_file, CliOverrides, Environment, StoredConfig,
     },
-    output::{print_value, OutputMode},
+    error::KalshiError,
+    output::{fmt_cents, print_rows, print_value, DisplayOptions, OutputMode},
+};
+
+/// Config commands run before any profile's preferences are applied.
+const PLAIN: DisplayOptions = DisplayOptions {
+    price_unit: None,
+    timezone: None,
+    table_width: None,
+    color: None,
 };
 
 #[derive(Debug, Clone, Args)]
@@ -17,22 +37,89 @@ pub struct ConfigCmd {
 
 #[derive(Debug, Clone, Subcommand)]
 enum ConfigSubcmd {
+    /// Interactively configure the selected profile (`--profile NAME`)
     Setup,
     Show,
     Path,
+    /// Delete the config file, or only the profile named by `--profile`
     Reset,
+    /// Make NAME the default profile
+    Use {
+        name: String,
+    },
+    /// List configured profiles
+    List,
+    /// Print one setting of the selected profile
+    Get {
+        key: String,
+    },
+    /// Set one setting of the selected profile
+    Set {
+        key: String,
+        value: String,
+    },
+    /// Remove one setting from the selected profile
+    Unset {
+        key: String,
+    },
+    /// Merge settings from a JSON file (`-` for stdin) into the config
+    Import {
+        path: String,
+    },
+    /// Print the selected profile as JSON
+    Export {
+        /// Export every profile in the config file format
+        #[arg(long)]
+        all: bool,
+        /// Write to this file (owner-only permissions) instead of stdout
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Check credentials, key file, clock and connectivity end to end
+    Doctor,
+    /// Generate an RSA key pair for a new Kalshi API key
+    Keygen {
+        /// RSA modulus size: 2048 or 4096
+        #[arg(long, default_value_t = 2048)]
+        bits: u32,
+        /// Private key path (default: <config dir>/<profile>.pem)
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        /// Overwrite an existing key file
+        #[arg(long)]
+        force: bool,
+        /// Record the key path as `api_secret_path` in the selected profile
+        #[arg(long)]
+        save: bool,
+    },
 }
 
-pub async fn run(cmd: ConfigCmd, mode: OutputMode) -> anyhow::Result<()> {
+pub async fn run(cmd: ConfigCmd, overrides: CliOverrides, mode: OutputMode) -> anyhow::Result<()> {
+    // Profile explicitly requested via `--profile`/`KALSHI_PROFILE`.
+    let profile = overrides.profile.clone();
     match cmd.command {
-        ConfigSubcmd::Setup => setup(mode),
-        ConfigSubcmd::Show => show(mode),
+        ConfigSubcmd::Setup => setup(profile.as_deref(), mode),
+        ConfigSubcmd::Show => show(overrides, mode),
         ConfigSubcmd::Path => path(mode),
-        ConfigSubcmd::Reset => reset(mode),
+        ConfigSubcmd::Reset => reset(profile.as_deref(), mode),
+        ConfigSubcmd::Use { name } => use_profile(&name, mode),
+        ConfigSubcmd::List => list(mode),
+        ConfigSubcmd::Get { key } => get(profile.as_deref(), &key, mode),
+        ConfigSubcmd::Set { key, value } => set(profile.as_deref(), &key, &value, mode),
+        ConfigSubcmd::Unset { key } => unset(profile.as_deref(), &key, mode),
+        ConfigSubcmd::Import { path } => import(profile.as_deref(), &path, mode),
+        ConfigSubcmd::Export { all, file } => export(profile.as_deref(), all, file, mode),
+        ConfigSubcmd::Doctor => doctor(overrides, mode).await,
+        ConfigSubcmd::Keygen {
+            bits,
+            out,
+            force,
+            save,
+        } => keygen(profile.as_deref(), bits, out, force, save, mode),
     }
 }
 
-fn setup(mode: OutputMode) -> anyhow::Result<()> {
+fn setup(profile: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
     let env_choices = ["prod", "demo"];
     let env_idx = Select::new()
         .with_prompt("Environment")
@@ -44,54 +131,770 @@ fn setup(mode: OutputMode) -> anyhow::Result<()> {
     let api_secret_path: String = Input::new()
         .with_prompt("API secret path (PEM)")
         .interact_text()?;
+    let api_secret_path = offer_key_encryption(api_secret_path)?;
 
-    let cfg = StoredConfig {
-        api_key: Some(api_key),
-        api_secret_path: Some(api_secret_path),
-        environment: Some(if env_idx == 0 {
+    let name = update_profile(profile, |cfg| {
+        cfg.api_key = Some(api_key);
+        cfg.api_secret_path = Some(api_secret_path);
+        cfg.environment = Some(if env_idx == 0 {
             Environment::Prod
         } else {
             Environment::Demo
-        }),
-    };
-
-    save_config(&cfg)?;
+        });
+        Ok(())
+    })?;
     print_value(
         mode,
-        &json!({"ok": true, "path": config_path()?.display().to_string()}),
+        &PLAIN,
+        &json!({"ok": true, "profile": name, "path": config_path()?.display().to_string()}),
     )
 }
 
-fn show(mode: OutputMode) -> anyhow::Result<()> {
-    let cfg = load_stored_config()?;
+/// Offers to write a passphrase-encrypted copy of a plaintext key and returns
+/// the path the config should point at.
+fn offer_key_encryption(path: String) -> anyhow::Result<String> {
+    let Ok(key) = fs::read_to_string(&path) else {
+        return Ok(path);
+    };
+    if is_encrypted_key(&key)
+        || !Confirm::new()
+            .with_prompt("Encrypt this private key with a passphrase?")
+            .default(false)
+            .interact()?
+    {
+        return Ok(path);
+    }
+
+    let target = encrypted_key_path(Path::new(&path));
+    if target.exists() {
+        anyhow::bail!("{} already exists", target.display());
+    }
+    let passphrase = Password::new()
+        .with_prompt("New passphrase")
+        .with_confirmation("Confirm passphrase", "Passphrases do not match")
+        .interact()?;
+    write_private_file(&target, &encrypt_private_key(&key, &passphrase)?)?;
+    eprintln!(
+        "Wrote encrypted key to {}. Delete the plaintext key once `kal portfolio balance` works.",
+        target.display()
+    );
+    Ok(target.display().to_string())
+}
+
+fn encrypted_key_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "kalshi".to_string());
+    path.with_file_name(format!("{stem}.encrypted.pem"))
+}
+
+fn show(overrides: CliOverrides, mode: OutputMode) -> anyhow::Result<()> {
+    let profile = overrides.profile.clone();
+    let profile = profile.as_deref();
+    let env_file = overrides.env.path().map(|p| p.display().to_string());
+    let file = load_config_file()?;
+    let cfg = file.profile(profile)?;
+    // Where each effective setting comes from (flag > env > env file > config
+    // file). Resolution can fail on a missing key file; the stored values are
+    // still worth showing then.
+    let sources = resolve_settings(overrides).ok().map(|settings| {
+        let sources = settings.sources;
+        json!({
+            "environment": sources.environment,
+            "apiKey": sources.api_key,
+            "apiSecret": sources.api_secret,
+            "baseUrl": sources.base_url,
+            "wsUrl": sources.ws_url,
+            "rateLimit": sources.rate_limit,
+        })
+    });
     let masked = json!({
-        "apiKey": cfg.api_key.as_ref().map(mask_secret),
+        "profile": file.active_profile_name(profile),
+        "apiKey": cfg.api_key.as_deref().map(mask_secret),
         "apiSecretPath": cfg.api_secret_path,
-        "environment": cfg.environment.map(|e| match e { Environment::Prod => "prod", Environment::Demo => "demo" }),
+        "environment": cfg.environment.map(env_name),
+        "baseUrl": cfg.base_url,
+        "wsUrl": cfg.ws_url,
+        "rateLimit": cfg.rate_limit,
+        "keyPassphrasePath": cfg.key_passphrase_path,
+        "preferences": cfg.preferences,
+        "envFile": env_file,
+        "sources": sources,
     });
-    print_value(mode, &masked)
+    print_value(mode, &PLAIN, &masked)
 }
 
 fn path(mode: OutputMode) -> anyhow::Result<()> {
-    print_value(mode, &json!({"path": config_path()?.display().to_string()}))
+    print_value(
+        mode,
+        &PLAIN,
+        &json!({"path": config_path()?.display().to_string()}),
+    )
 }
 
-fn reset(mode: OutputMode) -> anyhow::Result<()> {
+fn reset(profile: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    if let Some(name) = profile {
+        let mut file = load_config_file()?;
+        if !file.profiles.contains_key(name) {
+            return Err(KalshiError::config(format!(
+                "unknown profile `{name}`; see `kal config list`"
+            ))
+            .into());
+        }
+        let deleted = Confirm::new()
+            .with_prompt(format!("Delete profile `{name}`?"))
+            .default(false)
+            .interact()?;
+        if deleted {
+            file.profiles.remove(name);
+            if file.default_profile.as_deref() == Some(name) {
+                file.default_profile = None;
+            }
+            save_config_file(&file)?;
+        }
+        return print_value(mode, &PLAIN, &json!({"deleted": deleted, "profile": name}));
+    }
+
     if Confirm::new()
         .with_prompt("Delete config file?")
         .default(false)
         .interact()?
     {
         delete_config()?;
-        print_value(mode, &json!({"deleted": true}))
+        print_value(mode, &PLAIN, &json!({"deleted": true}))
     } else {
-        print_value(mode, &json!({"deleted": false}))
+        print_value(mode, &PLAIN, &json!({"deleted": false}))
     }
 }
 
-fn mask_secret(value: &String) -> String {
+fn use_profile(name: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let mut file = load_config_file()?;
+    if !file.profiles.contains_key(name) {
+        return Err(KalshiError::config(format!(
+            "unknown profile `{name}`; create it with `kal config setup --profile {name}`"
+        ))
+        .into());
+    }
+    file.default_profile = Some(name.to_string());
+    save_config_file(&file)?;
+    print_value(mode, &PLAIN, &json!({"ok": true, "defaultProfile": name}))
+}
+
+fn list(mode: OutputMode) -> anyhow::Result<()> {
+    let file = load_config_file()?;
+    let rows = file
+        .profiles
+        .iter()
+        .map(|(name, cfg)| {
+            json!({
+                "profile": name,
+                "default": file.default_profile.as_deref() == Some(name.as_str()),
+                "environment": cfg.environment,
+                "apiKey": cfg.api_key.as_deref().map(mask_secret),
+            })
+        })
+        .collect::<Vec<_>>();
+    print_rows(
+        mode,
+        &PLAIN,
+        &rows,
+        &["profile", "default", "environment", "apiKey"],
+    )
+}
+
+fn get(profile: Option<&str>, key: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let key = canonical_key(key)?;
+    let file = load_config_file()?;
+    let value = file.profile(profile)?.get(key)?;
+    match mode {
+        OutputMode::Json => print_value(
+            mode,
+            &PLAIN,
+            &json!({"profile": file.active_profile_name(profile), "key": key, "value": value}),
+        ),
+        // Bare value so `$(kal config get base_url)` works in scripts.
+        OutputMode::Table => {
+            println!("{}", value.unwrap_or_default());
+            Ok(())
+        }
+    }
+}
+
+fn set(profile: Option<&str>, key: &str, value: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let key = canonical_key(key)?;
+    let mut stored = None;
+    let name = update_profile(profile, |cfg| {
+        cfg.set(key, value)?;
+        stored = cfg.get(key)?;
+        Ok(())
+    })?;
+    print_value(
+        mode,
+        &PLAIN,
+        &json!({"ok": true, "profile": name, "key": key, "value": stored}),
+    )
+}
+
+fn unset(profile: Option<&str>, key: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let key = canonical_key(key)?;
+    let name = update_profile(profile, |cfg| cfg.unset(key))?;
+    print_value(
+        mode,
+        &PLAIN,
+        &json!({"ok": true, "profile": name, "key": key}),
+    )
+}
+
+/// Accepts either a single profile (`{"api_key": ...}`), merged into the
+/// selected profile, or a full config file (`{"profiles": {...}}`).
+fn import(profile: Option<&str>, path: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let content = if path == "-" {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        fs::read_to_string(path)
+            .map_err(|err| KalshiError::config(format!("cannot read {path}: {err}")))?
+    };
+    let value = serde_json::from_str::<Value>(&content)
+        .map_err(|err| KalshiError::config(format!("invalid JSON in {path}: {err}")))?;
+
+    let Some(profiles) = value.get("profiles") else {
+        let name = update_profile(profile, |cfg| apply_json(cfg, &value))?;
+        return print_value(mode, &PLAIN, &json!({"ok": true, "profiles": [name]}));
+    };
+
+    let profiles = profiles
+        .as_object()
+        .ok_or_else(|| KalshiError::config("`profiles` must be an object"))?;
+    let mut file = load_config_file()?;
+    for (name, settings) in profiles {
+        let mut cfg = file.profiles.get(name).cloned().unwrap_or_default();
+        apply_json(&mut cfg, settings)?;
+        file.profiles.insert(name.clone(), cfg);
+    }
+    if let Some(default) = value.get("default_profile").and_then(Value::as_str) {
+        if !file.profiles.contains_key(default) {
+            return Err(KalshiError::config(format!(
+                "default_profile `{default}` is not among the imported profiles"
+            ))
+            .into());
+        }
+        file.default_profile = Some(default.to_string());
+    }
+    save_config_file(&file)?;
+    print_value(
+        mode,
+        &PLAIN,
+        &json!({"ok": true, "profiles": profiles.keys().collect::<Vec<_>>()}),
+    )
+}
+
+fn export(
+    profile: Option<&str>,
+    all: bool,
+    path: Option<PathBuf>,
+    mode: OutputMode,
+) -> anyhow::Result<()> {
+    let file = load_config_file()?;
+    let body = if all {
+        serde_json::to_string_pretty(&file)?
+    } else {
+        serde_json::to_string_pretty(&file.profile(profile)?)?
+    };
+
+    match path {
+        Some(path) => {
+            write_private_file(&path, &body)?;
+            print_value(
+                mode,
+                &PLAIN,
+                &json!({"ok": true, "path": path.display().to_string()}),
+            )
+        }
+        None => {
+            println!("{body}");
+            Ok(())
+        }
+    }
+}
+
+/// Loads the config, applies `update` to the selected profile (creating it if
+/// needed) and saves. Returns the profile name.
+fn update_profile(
+    profile: Option<&str>,
+    update: impl FnOnce(&mut StoredConfig) -> anyhow::Result<()>,
+) -> anyhow::Result<String> {
+    let mut file = load_config_file()?;
+    let name = file.active_profile_name(profile);
+    let mut cfg = file.profiles.get(&name).cloned().unwrap_or_default();
+    update(&mut cfg)?;
+    file.profiles.insert(name.clone(), cfg);
+    if file.default_profile.is_none() {
+        file.default_profile = Some(name.clone());
+    }
+    save_config_file(&file)?;
+    Ok(name)
+}
+
+/// Applies a JSON object of settings through `StoredConfig::set`, so imports
+/// are validated like `config set`. `null` unsets a key.
+fn apply_json(cfg: &mut StoredConfig, settings: &Value) -> anyhow::Result<()> {
+    let settings = settings
+        .as_object()
+        .ok_or_else(|| KalshiError::config("expected a JSON object of config settings"))?;
+    for (key, value) in settings {
+        match value {
+            // Exports nest preference keys; they share the flat key namespace.
+            Value::Object(_) if key == "preferences" => apply_json(cfg, value)?,
+            Value::Null => cfg.unset(key)?,
+            Value::String(s) => cfg.set(key, s)?,
+            Value::Number(n) => cfg.set(key, &n.to_string())?,
+            Value::Bool(b) => cfg.set(key, &b.to_string())?,
+            _ => {
+                return Err(KalshiError::config(format!(
+                    "invalid value for {key}: expected a string"
+                ))
+                .into())
+            }
+        }
+    }
+    Ok(())
+}
+
+fn keygen(
+    profile: Option<&str>,
+    bits: u32,
+    out: Option<PathBuf>,
+    force: bool,
+    save: bool,
+    mode: OutputMode,
+) -> anyhow::Result<()> {
+    let name = load_config_file()?.active_profile_name(profile);
+    let out = match out {
+        Some(path) => path,
+        None => config_path()?.with_file_name(format!("{name}.pem")),
+    };
+    let public_path = out.with_extension("pub.pem");
+    // Checked up front for a clear message; `write_key_pair` re-checks
+    // atomically when it creates the files.
+    if !force {
+        if let Some(existing) = [&out, &public_path].into_iter().find(|p| p.exists()) {
+            return Err(key_exists(existing).into());
+        }
+    }
+
+    let (private_pem, public_pem) =
+        generate_key_pair(bits).map_err(|err| KalshiError::config(err.to_string()))?;
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let key_mode = write_key_pair(&out, &public_path, &private_pem, &public_pem, force)?;
+
+    let key_path = fs::canonicalize(&out)?.display().to_string();
+    if save {
+        let path = key_path.clone();
+        update_profile(profile, |cfg| {
+            cfg.api_secret_path = Some(path);
+            Ok(())
+        })?;
+    }
+
+    match mode {
+        OutputMode::Json => print_value(
+            mode,
+            &PLAIN,
+            &json!({
+                "privateKeyPath": key_path,
+                "publicKeyPath": public_path.display().to_string(),
+                "publicKey": public_pem,
+                "bits": bits,
+                "savedToProfile": save.then_some(name),
+            }),
+        ),
+        OutputMode::Table => {
+            match key_mode {
+                Some(key_mode) => println!("Private key: {key_path} (mode {key_mode:04o})"),
+                None => println!("Private key: {key_path}"),
+            }
+            println!("Public key:  {}", public_path.display());
+            if save {
+                println!("Saved as api_secret_path in profile `{name}`");
+            }
+            println!();
+            println!("Paste this public key into Kalshi's API key form, then set the key id with `kal config set api_key <id>`:");
+            println!();
+            print!("{public_pem}");
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    check: &'static str,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl Check {
+    fn new(check: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            status,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Runs each diagnostic in order, continuing past failures so the report
+/// shows everything that needs fixing at once.
+async fn doctor(overrides: CliOverrides, mode: OutputMode) -> anyhow::Result<()> {
+    use CheckStatus::*;
+
+    let mut checks = Vec::new();
+    let mut settings = match resolve_settings(overrides) {
+        Ok(settings) => settings,
+        Err(err) => {
+            checks.push(
+                Check::new("config", Fail, format!("{err:#}"))
+                    .hint("fix the flag, env var or config value named above (`kal config show`)"),
+            );
+            return finish_doctor(mode, checks);
+        }
+    };
+    let sources = &settings.sources;
+
+    checks.push(Check::new(
+        "profile",
+        Pass,
+        format!("{} ({})", sources.profile, config_path()?.display()),
+    ));
+    checks.push(Check::new(
+        "environment",
+        Pass,
+        format!(
+            "{} ({})",
+            env_name(settings.environment),
+            sources.environment
+        ),
+    ));
+    let base_url = settings
+        .base_url
+        .as_deref()
+        .unwrap_or_else(|| default_rest_base_url(settings.environment))
+        .to_string();
+    checks.push(Check::new(
+        "base_url",
+        Pass,
+        format!("{base_url} ({})", sources.base_url),
+    ));
+
+    checks.push(match &settings.api_key {
+        Some(key) => Check::new(
+            "api_key",
+            Pass,
+            format!("{} ({})", mask_secret(key), sources.api_key),
+        ),
+        None => Check::new("api_key", Fail, "not set")
+            .hint("set KALSHI_API_KEY, pass --api-key or run `kal config set api_key <id>`"),
+    });
+    checks.push(match (&settings.api_secret, &sources.api_secret_path) {
+        (Some(_), Some(path)) => Check::new(
+            "api_secret",
+            Pass,
+            format!("{} ({})", path.display(), sources.api_secret),
+        ),
+        (Some(_), None) => Check::new(
+            "api_secret",
+            Pass,
+            format!("inline key ({})", sources.api_secret),
+        ),
+        (None, _) => Check::new("api_secret", Fail, "not set").hint(
+            "set KALSHI_API_SECRET, pass --api-secret or run `kal config set api_secret_path <file>`",
+        ),
+    });
+    checks.push(key_permissions_check(sources.api_secret_path.as_deref()));
+
+    let (mut runtime, key_error) = match settings.clone().into_runtime() {
+        Ok(runtime) => (runtime, None),
+        Err(err) => {
+            settings.api_secret = None;
+            (settings.into_runtime()?, Some(err))
+        }
+    };
+    let credentials = if key_error.is_some() || runtime.credentials.is_some() {
+        let loaded = match key_error {
+            Some(err) => Err(err),
+            None => ensure_auth(&runtime),
+        };
+        match loaded {
+            Ok(creds) => {
+                checks.push(Check::new(
+                    "key_format",
+                    Pass,
+                    format!("{}-bit RSA ({})", creds.key_bits(), creds.key_format()),
+                ));
+                let path = format!("{}/portfolio/balance", runtime.rest_sign_prefix());
+                checks.push(match creds.sign(0, "GET", &path) {
+                    Ok(_) => {
+                        Check::new("test_signature", Pass, "RSA-PSS/SHA-256 signature created")
+                    }
+                    Err(err) => Check::new("test_signature", Fail, format!("{err:#}")),
+                });
+                runtime.credentials.clone()
+            }
+            Err(err) => {
+                checks.push(Check::new("key_format", Fail, format!("{err:#}")).hint(
+                    "expected the RSA key downloaded with the API key (PKCS#1/PKCS#8, >= 2048 bits); encrypted keys need KALSHI_KEY_PASSPHRASE",
+                ));
+                // Keep going without credentials so connectivity is still checked.
+                runtime.credentials = None;
+                None
+            }
+        }
+    } else {
+        checks.push(Check::new("key_format", Skip, "no credentials"));
+        checks.push(Check::new("test_signature", Skip, "no credentials"));
+        None
+    };
+
+    let client = KalshiClient::new(runtime)?;
+    checks.push(match client.clock_skew_ms().await {
+        Ok(skew) => {
+            let detail = format!("{:+.1}s vs exchange", skew as f64 / 1000.0);
+            match skew.abs() {
+                0..=2_000 => Check::new("clock_skew", Pass, detail),
+                _ => Check::new("clock_skew", Warn, detail).hint(
+                    "kal offsets signing timestamps automatically; enable NTP time sync to fix the clock",
+                ),
+            }
+        }
+        Err(err) => Check::new("clock_skew", Fail, format!("{err:#}"))
+            .hint(format!("check network access to {base_url}")),
+    });
+
+    checks.push(match client.get_public("/exchange/status", None).await {
+        Ok(status) => Check::new(
+            "exchange_status",
+            Pass,
+            format!(
+                "exchange_active={} trading_active={}",
+                status.get("exchange_active").unwrap_or(&Value::Null),
+                status.get("trading_active").unwrap_or(&Value::Null)
+            ),
+        ),
+        Err(err) => Check::new("exchange_status", Fail, format!("{err:#}"))
+            .hint("check --base-url/--env and network access"),
+    });
+
+    checks.push(if credentials.is_none() {
+        Check::new("authenticated_request", Skip, "no valid credentials")
+    } else {
+        match client.get_balance().await {
+            Ok(balance) => Check::new(
+                "authenticated_request",
+                Pass,
+                format!("balance {}", fmt_cents(balance.balance)),
+            ),
+            Err(err) => {
+                let hint = match err.downcast_ref::<KalshiError>() {
+                    Some(KalshiError::Auth(_)) => {
+                        "key id and private key do not match, the key was revoked, or it belongs to the other environment (demo keys only work with --env demo)"
+                    }
+                    _ => "see the error above; rerun with --trace-http for details",
+                };
+                Check::new("authenticated_request", Fail, format!("{err:#}")).hint(hint)
+            }
+        }
+    });
+
+    finish_doctor(mode, checks)
+}
+
+/// Writes a generated key pair. Without `force` neither file may exist yet,
+/// and a private key written for a public key that could not be is removed.
+fn write_key_pair(
+    private_path: &Path,
+    public_path: &Path,
+    private_pem: &str,
+    public_pem: &str,
+    force: bool,
+) -> anyhow::Result<Option<u32>> {
+    let exists = |path: &Path, err: std::io::Error| -> anyhow::Error {
+        if err.kind() == std::io::ErrorKind::AlreadyExists {
+            key_exists(path).into()
+        } else {
+            err.into()
+        }
+    };
+
+    if force {
+        write_private_file(private_path, private_pem)?;
+        fs::write(public_path, public_pem)?;
+    } else {
+        create_private_file(private_path, private_pem).map_err(|err| exists(private_path, err))?;
+        let public = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(public_path)
+            .and_then(|mut file| file.write_all(public_pem.as_bytes()));
+        if let Err(err) = public {
+            let _ = fs::remove_file(private_path);
+            return Err(exists(public_path, err));
+        }
+    }
+    restrict_to_owner(private_path)
+}
+
+/// Sets `path` to mode 0600 and returns the mode read back, so callers only
+/// report what is actually on disk. `None` where modes do not apply.
+fn restrict_to_owner(path: &Path) -> anyhow::Result<Option<u32>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        let mode = fs::metadata(path)?.permissions().mode() & 0o777;
+        if mode != 0o600 {
+            anyhow::bail!("{} has mode {mode:o}, expected 600", path.display());
+        }
+        Ok(Some(mode))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(None)
+    }
+}
+
+fn key_exists(path: &Path) -> KalshiError {
+    KalshiError::config(format!(
+        "{} already exists; pass --force to overwrite it",
+        path.display()
+    ))
+}
+
+fn key_permissions_check(path: Option<&Path>) -> Check {
+    let Some(path) = path else {
+        return Check::new("key_permissions", CheckStatus::Skip, "no key file");
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        match fs::metadata(path) {
+            Ok(meta) => {
+                let mode = meta.permissions().mode() & 0o777;
+                if mode & 0o077 == 0 {
+                    Check::new("key_permissions", CheckStatus::Pass, format!("{mode:o}"))
+                } else {
+                    Check::new(
+                        "key_permissions",
+                        CheckStatus::Warn,
+                        format!("{mode:o}: readable by other users"),
+                    )
+                    .hint(format!("chmod 600 {}", path.display()))
+                }
+            }
+            Err(err) => Check::new("key_permissions", CheckStatus::Fail, err.to_string()),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        Check::new(
+            "key_permissions",
+            CheckStatus::Skip,
+            format!("not checked on this platform ({})", path.display()),
+        )
+    }
+}
+
+fn finish_doctor(mode: OutputMode, checks: Vec<Check>) -> anyhow::Result<()> {
+    print_rows(
+        mode,
+        &PLAIN,
+        &checks,
+        &["check", "status", "detail", "hint"],
+    )?;
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(KalshiError::config(format!("{failed} doctor check(s) failed")).into());
+    }
+    Ok(())
+}
+
+fn mask_secret(value: &str) -> String {
     if value.len() <= 6 {
         return "***".to_string();
     }
     format!("{}***{}", &value[..3], &value[value.len() - 3..])
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::write_key_pair;
+
+    #[test]
+    fn key_pairs_never_overwrite_without_force() {
+        let dir = std::env::temp_dir().join(format!("kal-keygen-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let private = dir.join("key.pem");
+        let public = dir.join("key.pub.pem");
+
+        write_key_pair(&private, &public, "private", "public", false).unwrap();
+        assert_eq!(fs::read_to_string(&public).unwrap(), "public");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&private).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let err = write_key_pair(&private, &public, "new", "new", false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{err}");
+        assert_eq!(fs::read_to_string(&private).unwrap(), "private");
+
+        // A stray public key blocks the pair and leaves no private key behind.
+        fs::remove_file(&private).unwrap();
+        assert!(write_key_pair(&private, &public, "new", "new", false).is_err());
+        assert!(!private.exists());
+        assert_eq!(fs::read_to_string(&public).unwrap(), "public");
+
+        fs::write(&private, "world-readable").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&private, fs::Permissions::from_mode(0o644)).unwrap();
+        }
+        let mode = write_key_pair(&private, &public, "new", "new", true).unwrap();
+        #[cfg(unix)]
+        assert_eq!(mode, Some(0o600), "--force tightens an existing key");
+        assert_eq!(fs::read_to_string(&private).unwrap(), "new");
+        assert_eq!(fs::read_to_string(&public).unwrap(), "new");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,16 +1,14 @@
 use std::collections::BTreeMap;
 use std::fmt;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::{ArgAction, Args, Subcommand, ValueEnum};
-use serde_json::{Map, Value};
 
+use super::{PageArgs, RowArgs};
 use crate::{
-    client::KalshiClient,
-    output::{
-        extract_array, get_i64, print_value, render_events_table, render_events_top_table,
-        OutputMode,
-    },
+    client::{KalshiClient, PageOpts},
+    clock::now_secs,
+    models::Event,
+    output::{print_value, render_events_table, render_events_top_table, OutputMode},
     query::QueryParams,
     AppContext,
 };
@@ -48,6 +46,10 @@ enum EventsSubcmd {
         series_ticker: Option<String>,
         #[arg(long = "with-markets", default_value_t = false)]
         with_markets: bool,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Get {
         ticker: String,
@@ -55,8 +57,9 @@ enum EventsSubcmd {
         with_markets: bool,
     },
     Top {
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        /// Rows to show [default: 25, or the profile's `limit` preference]
+        #[arg(long)]
+        limit: Option<usize>,
         #[arg(long, default_value_t = 7)]
         days: i64,
         #[arg(long, default_value_t = 0)]
@@ -69,41 +72,48 @@ enum EventsSubcmd {
         include_mve: bool,
         #[arg(long, default_value_t = 1000)]
         universe: usize,
+        #[command(flatten)]
+        rows: RowArgs,
     },
 }
 
 pub async fn run(ctx: &AppContext, cmd: EventsCmd) -> anyhow::Result<()> {
     let client = KalshiClient::new(ctx.runtime.clone())?;
+    let prefs = &ctx.runtime.preferences;
 
     match cmd.command {
         EventsSubcmd::List {
             status,
             series_ticker,
             with_markets,
+            page,
+            rows,
         } => {
             // Table mode needs market counts; fetch nested markets automatically.
             let include_markets = with_markets || matches!(ctx.output_mode, OutputMode::Table);
             let status_text = status.as_ref().map(|s| s.to_string());
             let q = QueryParams::new()
-                .limit(100)
                 .optional("status", status_text.as_deref())
                 .optional("series_ticker", series_ticker)
                 .optional("with_nested_markets", include_markets.then_some("true"))
                 .build_always();
 
-            let data = client.get_public("/events", Some(q)).await?;
-            let events = extract_array(&data, "events");
-            render_events_table(ctx.output_mode, &events, status_text.as_deref())
+            let mut events: Vec<Event> =
+                client.collect_pages(q, page.opts(prefs, None, 100)).await?;
+            rows.apply(&mut events)?;
+            render_events_table(
+                ctx.output_mode,
+                &ctx.display,
+                &events,
+                status_text.as_deref(),
+            )
         }
         EventsSubcmd::Get {
             ticker,
             with_markets,
         } => {
-            let q = QueryParams::new()
-                .optional("with_nested_markets", with_markets.then_some("true"))
-                .build();
-            let data = client.get_public(&format!("/events/{ticker}"), q).await?;
-            print_value(ctx.output_mode, &data)
+            let data = client.get_event(&ticker, with_markets).await?;
+            print_value(ctx.output_mode, &ctx.display, &data)
         }
         EventsSubcmd::Top {
             limit,
@@ -113,6 +123,7 @@ pub async fn run(ctx: &AppContext, cmd: EventsCmd) -> anyhow::Result<()> {
             active,
             include_mve,
             universe,
+            rows: row_args,
         } => {
             let target_universe = universe.clamp(1, 10_000);
             let q = QueryParams::new()
@@ -124,10 +135,11 @@ pub async fn run(ctx: &AppContext, cmd: EventsCmd) -> anyhow::Result<()> {
             let events = fetch_events_universe(&client, q, target_universe).await?;
             let mut rows = aggregate_events(events, days, min_open_interest, min_total_volume);
             sort_top_events(&mut rows);
-            rows.truncate(limit);
+            row_args.apply(&mut rows)?;
+            rows.truncate(prefs.limit_or(limit, 25));
             let status_fallback = Some(if active { "open" } else { "closed" });
 
-            render_events_top_table(ctx.output_mode, &rows, status_fallback)
+            render_events_top_table(ctx.output_mode, &ctx.display, &rows, status_fallback)
         }
     }
 }
@@ -136,59 +148,26 @@ async fn fetch_events_universe(
     client: &KalshiClient,
     base_query: BTreeMap<String, String>,
     target_universe: usize,
-) -> anyhow::Result<Vec<Value>> {
-    let mut all_events: Vec<Value> = Vec::with_capacity(target_universe.min(2000));
-    let mut cursor: Option<String> = None;
-    let page_size = 200usize.min(target_universe.max(1));
-
-    loop {
-        let mut q = base_query.clone();
-        q.insert("limit".to_string(), page_size.to_string());
-        if let Some(c) = &cursor {
-            q.insert("cursor".to_string(), c.clone());
-        }
-
-        let data = client.get_public("/events", Some(q)).await?;
-        let page = extract_array(&data, "events");
-
-        if page.is_empty() {
-            break;
-        }
-        all_events.extend(page);
-        if all_events.len() >= target_universe {
-            break;
-        }
-
-        cursor = data
-            .get("cursor")
-            .and_then(Value::as_str)
-            .map(str::to_string)
-            .filter(|c| !c.is_empty());
-        if cursor.is_none() {
-            break;
-        }
-    }
-
-    all_events.truncate(target_universe);
-    Ok(all_events)
+) -> anyhow::Result<Vec<Event>> {
+    let opts = PageOpts {
+        page_size: None,
+        max_items: Some(target_universe),
+    };
+    client.collect_pages(base_query, opts).await
 }
 
 fn aggregate_events(
-    events: Vec<Value>,
+    events: Vec<Event>,
     days: i64,
     min_open_interest: i64,
     min_total_volume: i64,
-) -> Vec<Value> {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
-    let horizon = now + days.max(1) * 86_400;
+) -> Vec<Event> {
+    let horizon = now_secs() + days.max(1) * 86_400;
     let enforce_horizon = days > 0;
 
     let mut rows = Vec::with_capacity(events.len());
-    for event in events {
-        let markets = extract_array(&event, "markets");
+    for mut event in events {
+        let markets = event.markets.as_deref().unwrap_or_default();
         if markets.is_empty() {
             continue;
         }
@@ -199,11 +178,11 @@ fn aggregate_events(
         let mut has_upcoming = false;
 
         for market in markets {
-            total_volume += get_i64(&market, "volume").unwrap_or(0);
-            open_interest += get_i64(&market, "open_interest").unwrap_or(0);
+            total_volume += market.volume.unwrap_or(0);
+            open_interest += market.open_interest.unwrap_or(0);
             market_count += 1;
 
-            if let Some(close_ts) = market_close_ts(&market) {
+            if let Some(close_ts) = market.close_ts() {
                 if close_ts <= horizon {
                     has_upcoming = true;
                 }
@@ -219,43 +198,26 @@ fn aggregate_events(
             continue;
         }
 
-        let mut row = event.as_object().cloned().unwrap_or_else(Map::new);
-        row.insert("market_count".to_string(), Value::from(market_count));
-        row.insert("total_volume".to_string(), Value::from(total_volume));
-        row.insert("open_interest".to_string(), Value::from(open_interest));
-        rows.push(Value::Object(row));
+        event.market_count = Some(market_count);
+        event.total_volume = Some(total_volume);
+        event.open_interest = Some(open_interest);
+        rows.push(event);
     }
 
     rows
 }
 
-fn sort_top_events(rows: &mut [Value]) {
+fn sort_top_events(rows: &mut [Event]) {
     rows.sort_by(|a, b| {
-        let a_oi = get_i64(a, "open_interest").unwrap_or(0);
-        let b_oi = get_i64(b, "open_interest").unwrap_or(0);
-        let a_vol = get_i64(a, "total_volume").unwrap_or(0);
-        let b_vol = get_i64(b, "total_volume").unwrap_or(0);
-        let a_cnt = get_i64(a, "market_count").unwrap_or(0);
-        let b_cnt = get_i64(b, "market_count").unwrap_or(0);
+        let a_oi = a.open_interest.unwrap_or(0);
+        let b_oi = b.open_interest.unwrap_or(0);
+        let a_vol = a.total_volume.unwrap_or(0);
+        let b_vol = b.total_volume.unwrap_or(0);
+        let a_cnt = a.market_count.unwrap_or(0);
+        let b_cnt = b.market_count.unwrap_or(0);
 
         b_oi.cmp(&a_oi)
             .then_with(|| b_vol.cmp(&a_vol))
             .then_with(|| b_cnt.cmp(&a_cnt))
     });
 }
-
-fn market_close_ts(market: &Value) -> Option<i64> {
-    for key in ["close_time", "close_ts", "expiration_ts", "expiration_time"] {
-        if let Some(v) = market.get(key) {
-            if let Some(n) = v.as_i64() {
-                return Some(n);
-            }
-            if let Some(s) = v.as_str() {
-                if let Ok(n) = s.parse::<i64>() {
-                    return Some(n);
-                }
-            }
-        }
-    }
-    None
-}
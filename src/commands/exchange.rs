@@ -24,5 +24,5 @@ pub async fn run(ctx: &AppContext, cmd: ExchangeCmd) -> anyhow::Result<()> {
         ExchangeSubcmd::Announcements => client.get_public("/exchange/announcements", None).await?,
     };
 
-    print_value(ctx.output_mode, &result)
+    print_value(ctx.output_mode, &ctx.display, &result)
 }
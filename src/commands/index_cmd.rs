@@ -0,0 +1,72 @@
+use clap::{Args, Subcommand};
+use serde_json::json;
+
+use crate::{
+    client::KalshiClient,
+    config::{env_name, index_path},
+    index::{self, MarketIndex, SyncError},
+    output::print_value,
+    AppContext,
+};
+
+#[derive(Debug, Clone, Args)]
+pub struct IndexCmd {
+    #[command(subcommand)]
+    command: IndexSubcmd,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum IndexSubcmd {
+    /// Fetch open markets changed since the last sync, plus open events and series
+    Sync {
+        /// Rebuild from every open market instead of only changed ones
+        #[arg(long)]
+        full: bool,
+    },
+    /// Show where the index is, when it was synced and what it holds
+    Status,
+}
+
+pub async fn run(ctx: &AppContext, cmd: IndexCmd) -> anyhow::Result<()> {
+    match cmd.command {
+        IndexSubcmd::Sync { full } => {
+            let client = KalshiClient::new(ctx.runtime.clone())?;
+            let report = index::sync(&client, &ctx.runtime, full).await?;
+            print_value(ctx.output_mode, &ctx.display, &report)
+        }
+        IndexSubcmd::Status => {
+            let path = index_path(env_name(ctx.runtime.environment))?;
+            let refresh_after = ctx.runtime.preferences.index_refresh_after_secs();
+            let last_error = SyncError::load(&ctx.runtime)
+                .map(|err| -> anyhow::Result<_> {
+                    Ok(json!({
+                        "at": jiff::Timestamp::from_second(err.at)?.to_string(),
+                        "message": err.message,
+                    }))
+                })
+                .transpose()?;
+            let mut status = match MarketIndex::load(&ctx.runtime)? {
+                Some(index) => json!({
+                    "path": path,
+                    "environment": env_name(ctx.runtime.environment),
+                    "base_url": index.base_url,
+                    "synced_at": jiff::Timestamp::from_second(index.synced_at)?.to_string(),
+                    "age_secs": index.age_secs(),
+                    "stale": refresh_after.is_some_and(|max| index.age_secs() > max),
+                    "markets": index.markets.len(),
+                    "events": index.events.len(),
+                    "series": index.series.len(),
+                }),
+                None => json!({
+                    "path": path,
+                    "environment": env_name(ctx.runtime.environment),
+                    "base_url": ctx.runtime.rest_base_url(),
+                    "synced_at": null,
+                    "hint": "run `kal index sync` to build the index",
+                }),
+            };
+            status["last_sync_error"] = last_error.into();
+            print_value(ctx.output_mode, &ctx.display, &status)
+        }
+    }
+}
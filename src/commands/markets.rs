@@ -1,15 +1,21 @@
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::{ArgAction, Args, Subcommand, ValueEnum};
-use serde_json::Value;
 
+use super::{PageArgs, RowArgs};
 use crate::{
-    client::KalshiClient,
-    output::{extract_array, get_i64, print_value, render_markets_table, render_markets_top_table},
+    client::{KalshiClient, PageOpts},
+    clock::now_secs,
+    index::{spawn_background_sync, MarketIndex, SyncError},
+    models::{Event, Market},
+    output::{
+        print_candles_csv, render_candles_table, render_market_detail, render_markets_table,
+        render_markets_top_table, render_orderbook, OutputMode,
+    },
     query::QueryParams,
+    search::{FieldKind, SearchAliases, SearchQuery},
     AppContext,
 };
 
@@ -37,12 +43,6 @@ const SEARCH_PAGE_SIZE: usize = 1000;
 const SEARCH_DEFAULT_LIMIT: usize = 25;
 const SEARCH_FALLBACK_DAYS: i64 = 30;
 
-#[derive(Debug, Clone)]
-struct FuzzyQuery {
-    normalized: String,
-    tokens: Vec<String>,
-}
-
 #[derive(Debug, Clone, Args)]
 pub struct MarketsCmd {
     #[command(subcommand)]
@@ -58,10 +58,16 @@ enum MarketsSubcmd {
         active: Option<bool>,
         #[arg(long = "event")]
         event_ticker: Option<String>,
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
-        #[arg(long, default_value_t = true)]
-        compact: bool,
+        /// Rows to show [default: 25, or the profile's `limit` preference]
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Compact table [default: true, or the profile's `compact` preference]
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", action = ArgAction::Set)]
+        compact: Option<bool>,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Get {
         ticker: String,
@@ -70,14 +76,22 @@ enum MarketsSubcmd {
         query: String,
         #[arg(long, default_value_t = 3)]
         days: i64,
-        #[arg(long, default_value_t = SEARCH_DEFAULT_LIMIT)]
-        limit: usize,
-        #[arg(long, default_value_t = true)]
-        compact: bool,
+        /// Rows to show [default: 25, or the profile's `limit` preference]
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Compact table [default: true, or the profile's `compact` preference]
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", action = ArgAction::Set)]
+        compact: Option<bool>,
+        /// Query the API even when a local market index exists
+        #[arg(long)]
+        live: bool,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Top {
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        /// Rows to show [default: 25, or the profile's `limit` preference]
+        #[arg(long)]
+        limit: Option<usize>,
         #[arg(long, default_value_t = 7)]
         days: i64,
         #[arg(long, default_value_t = 0)]
@@ -90,16 +104,66 @@ enum MarketsSubcmd {
         include_mve: bool,
         #[arg(long, default_value_t = 1000)]
         universe: usize,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Orderbook {
         ticker: String,
         #[arg(long)]
         depth: Option<usize>,
     },
+    /// Price history as OHLC candles (yes bid, yes ask and traded price)
+    Candles {
+        ticker: String,
+        #[arg(long, value_enum, default_value = "1h")]
+        interval: CandleInterval,
+        /// Start: unix seconds, YYYY-MM-DD, RFC 3339 or an age like 6h/7d
+        /// [default: 1 day (1m), 7 days (1h) or 90 days (1d) ago]
+        #[arg(long, value_parser = parse_time_arg)]
+        since: Option<i64>,
+        /// End, same formats as --since [default: now]
+        #[arg(long, value_parser = parse_time_arg)]
+        until: Option<i64>,
+        /// Series of the market [default: looked up from its event]
+        #[arg(long = "series")]
+        series_ticker: Option<String>,
+        /// Print CSV instead of a table
+        #[arg(long)]
+        csv: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CandleInterval {
+    #[value(name = "1m")]
+    Minute,
+    #[value(name = "1h")]
+    Hour,
+    #[value(name = "1d")]
+    Day,
+}
+
+impl CandleInterval {
+    fn minutes(self) -> u32 {
+        match self {
+            Self::Minute => 1,
+            Self::Hour => 60,
+            Self::Day => 1440,
+        }
+    }
+
+    fn default_lookback_secs(self) -> i64 {
+        match self {
+            Self::Minute => 86_400,
+            Self::Hour => 7 * 86_400,
+            Self::Day => 90 * 86_400,
+        }
+    }
 }
 
 pub async fn run(ctx: &AppContext, cmd: MarketsCmd) -> anyhow::Result<()> {
     let client = KalshiClient::new(ctx.runtime.clone())?;
+    let prefs = &ctx.runtime.preferences;
 
     match cmd.command {
         MarketsSubcmd::List {
@@ -108,6 +172,8 @@ pub async fn run(ctx: &AppContext, cmd: MarketsCmd) -> anyhow::Result<()> {
             event_ticker,
             limit,
             compact,
+            page,
+            rows,
         } => {
             let status_filter = if let Some(is_active) = active {
                 Some(if is_active { "open" } else { "closed" }.to_string())
@@ -115,38 +181,101 @@ pub async fn run(ctx: &AppContext, cmd: MarketsCmd) -> anyhow::Result<()> {
                 status.map(|s| s.to_string())
             };
             let q = QueryParams::new()
-                .limit(limit)
                 .optional("status", status_filter)
                 .optional("event_ticker", event_ticker)
                 .build_always();
 
-            let data = client.get_public("/markets", Some(q)).await?;
-            let mut markets = extract_array(&data, "markets");
+            let mut markets = client.collect_pages(q, page.opts(prefs, limit, 25)).await?;
             sort_markets(&mut markets);
             enrich_event_market_counts(&client, &mut markets).await?;
-
-            render_markets_table(ctx.output_mode, &markets, compact)
+            rows.apply(&mut markets)?;
+
+            render_markets_table(
+                ctx.output_mode,
+                &ctx.display,
+                &markets,
+                prefs.compact_or(compact),
+            )
         }
         MarketsSubcmd::Get { ticker } => {
-            let data = client
-                .get_public(&format!("/markets/{ticker}"), None)
-                .await?;
-            print_value(ctx.output_mode, &data)
+            let market = client.get_market(&ticker).await?;
+            if ctx.output_mode == OutputMode::Json {
+                return render_market_detail(ctx.output_mode, &ctx.display, &market, None, None);
+            }
+
+            // The parent event and series only add context; show the market
+            // even if either lookup fails.
+            let event = match market.event_ticker.as_deref() {
+                Some(event_ticker) => client
+                    .get_event(event_ticker, false)
+                    .await
+                    .ok()
+                    .map(|detail| detail.into_inner().event),
+                None => None,
+            };
+            let series = match event.as_ref().and_then(|e| e.series_ticker.as_deref()) {
+                Some(series_ticker) => client.get_series(series_ticker).await.ok(),
+                None => None,
+            };
+            render_market_detail(
+                ctx.output_mode,
+                &ctx.display,
+                &market,
+                event.as_ref(),
+                series.as_ref(),
+            )
         }
         MarketsSubcmd::Search {
             query,
             days,
             limit,
             compact,
+            live,
+            rows,
         } => {
-            let mut markets = search_open_markets(&client, &query, Some(days)).await?;
-            if markets.is_empty() {
-                markets = search_open_markets(&client, &query, Some(SEARCH_FALLBACK_DAYS)).await?;
+            let search = SearchQuery::new(&query, &SearchAliases::load()?);
+            let index = if live { None } else { load_search_index(ctx) };
+
+            let mut markets = match &index {
+                Some(index) => {
+                    let mut found = search_market_index(index, &query, &search, Some(days));
+                    if found.is_empty() {
+                        found =
+                            search_market_index(index, &query, &search, Some(SEARCH_FALLBACK_DAYS));
+                    }
+                    found
+                }
+                None => {
+                    let mut found =
+                        search_open_markets(&client, &query, &search, Some(days)).await?;
+                    if found.is_empty() {
+                        found = search_open_markets(
+                            &client,
+                            &query,
+                            &search,
+                            Some(SEARCH_FALLBACK_DAYS),
+                        )
+                        .await?;
+                    }
+                    found
+                }
+            };
+            sort_markets_by_relevance(&mut markets);
+            rows.apply(&mut markets)?;
+            markets.truncate(prefs.limit_or(limit, SEARCH_DEFAULT_LIMIT));
+            if index.is_none() {
+                enrich_event_market_counts(&client, &mut markets).await?;
             }
-            sort_markets_by_volume(&mut markets);
-            markets.truncate(limit);
-            enrich_event_market_counts(&client, &mut markets).await?;
-            render_markets_table(ctx.output_mode, &markets, compact)
+            render_markets_table(
+                ctx.output_mode,
+                &ctx.display,
+                &markets,
+                prefs.compact_or(compact),
+            )?;
+            if let (Some(index), OutputMode::Table) = (&index, ctx.output_mode) {
+                eprintln!("(from index, synced {}m ago)", index.age_secs() / 60);
+            }
+            Ok(())
         }
         MarketsSubcmd::Top {
             limit,
@@ -156,6 +285,7 @@ pub async fn run(ctx: &AppContext, cmd: MarketsCmd) -> anyhow::Result<()> {
             active,
             include_mve,
             universe,
+            rows,
         } => {
             let target_universe = universe.clamp(1, 10_000);
             let q = QueryParams::new()
@@ -166,50 +296,146 @@ pub async fn run(ctx: &AppContext, cmd: MarketsCmd) -> anyhow::Result<()> {
 
             let mut markets = fetch_markets_universe(&client, q, target_universe).await?;
             markets.retain(|m| {
-                let oi = get_i64(m, "open_interest").unwrap_or(0);
-                let vol = get_i64(m, "volume").unwrap_or(0);
+                let oi = m.open_interest.unwrap_or(0);
+                let vol = m.volume.unwrap_or(0);
                 oi >= min_open_interest && vol >= min_total_volume
             });
             sort_markets_by_oi_volume(&mut markets);
-            markets.truncate(limit);
+            rows.apply(&mut markets)?;
+            markets.truncate(prefs.limit_or(limit, 25));
             enrich_event_market_counts(&client, &mut markets).await?;
 
-            render_markets_top_table(ctx.output_mode, &markets)
+            render_markets_top_table(ctx.output_mode, &ctx.display, &markets)
         }
         MarketsSubcmd::Orderbook { ticker, depth } => {
-            let q = QueryParams::new().optional("depth", depth).build();
-            let data = client
-                .get_public(&format!("/markets/{ticker}/orderbook"), q)
+            let data = client.get_orderbook(&ticker, depth).await?;
+            render_orderbook(ctx.output_mode, &ctx.display, &ticker, &data, depth)
+        }
+        MarketsSubcmd::Candles {
+            ticker,
+            interval,
+            since,
+            until,
+            series_ticker,
+            csv,
+        } => {
+            let end_ts = until.unwrap_or_else(now_secs);
+            let start_ts = since.unwrap_or(end_ts - interval.default_lookback_secs());
+            if start_ts >= end_ts {
+                anyhow::bail!("--since must be earlier than --until");
+            }
+            let series_ticker = match series_ticker {
+                Some(series) => series,
+                None => market_series_ticker(&client, &ticker).await?,
+            };
+
+            let candles = client
+                .get_market_candlesticks(
+                    &series_ticker,
+                    &ticker,
+                    start_ts,
+                    end_ts,
+                    interval.minutes(),
+                )
                 .await?;
-            print_value(ctx.output_mode, &data)
+            if csv {
+                print_candles_csv(&candles);
+                Ok(())
+            } else {
+                render_candles_table(ctx.output_mode, &ctx.display, &candles)
+            }
         }
     }
 }
 
-fn sort_markets(markets: &mut [Value]) {
+/// Candlesticks are addressed by series; markets only name their event.
+async fn market_series_ticker(client: &KalshiClient, ticker: &str) -> anyhow::Result<String> {
+    let market = client.get_market(ticker).await?;
+    let event_ticker = market
+        .into_inner()
+        .event_ticker
+        .ok_or_else(|| anyhow::anyhow!("market {ticker} has no event; pass --series"))?;
+    let event = client.get_event(&event_ticker, false).await?;
+    event
+        .into_inner()
+        .event
+        .into_inner()
+        .series_ticker
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("event {event_ticker} has no series; pass --series"))
+}
+
+/// Unix seconds from a timestamp, a date (UTC midnight), an RFC 3339 time or
+/// an age such as `90m`, `6h`, `7d` or `2w`.
+fn parse_time_arg(raw: &str) -> Result<i64, String> {
+    let raw = raw.trim();
+    if raw.eq_ignore_ascii_case("now") {
+        return Ok(now_secs());
+    }
+    if let Ok(secs) = raw.parse::<i64>() {
+        return Ok(secs);
+    }
+    if let Some(unit) = raw.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        if let Ok(n) = raw[..raw.len() - 1].parse::<i64>() {
+            let secs = match unit {
+                'm' => 60,
+                'h' => 3_600,
+                'd' => 86_400,
+                'w' => 7 * 86_400,
+                _ => {
+                    return Err(format!(
+                        "unknown unit `{unit}` in `{raw}`; use m, h, d or w"
+                    ))
+                }
+            };
+            return n
+                .checked_mul(secs)
+                .and_then(|age| now_secs().checked_sub(age))
+                .ok_or_else(|| format!("`{raw}` is out of range"));
+        }
+    }
+    if let Ok(date) = raw.parse::<jiff::civil::Date>() {
+        return date
+            .to_zoned(jiff::tz::TimeZone::UTC)
+            .map(|z| z.timestamp().as_second())
+            .map_err(|err| err.to_string());
+    }
+    raw.parse::<jiff::Timestamp>()
+        .map(|ts| ts.as_second())
+        .map_err(|_| {
+            format!(
+                "invalid time `{raw}`; use unix seconds, YYYY-MM-DD, RFC 3339 or an age like 7d"
+            )
+        })
+}
+
+fn sort_markets(markets: &mut [Market]) {
     markets.sort_by(|a, b| {
-        let av = market_volume(a);
-        let bv = market_volume(b);
+        let av = a.ranking_volume();
+        let bv = b.ranking_volume();
         bv.cmp(&av)
     });
 }
 
-fn sort_markets_by_volume(markets: &mut [Value]) {
+/// Search score, highest first, with volume breaking ties.
+fn sort_markets_by_relevance(markets: &mut [Market]) {
     markets.sort_by(|a, b| {
-        let av = market_volume(a);
-        let bv = market_volume(b);
-        bv.cmp(&av)
+        let a_score = a.search_score.unwrap_or(0.0);
+        let b_score = b.search_score.unwrap_or(0.0);
+        b_score
+            .total_cmp(&a_score)
+            .then_with(|| b.ranking_volume().cmp(&a.ranking_volume()))
     });
 }
 
-fn sort_markets_by_oi_volume(markets: &mut [Value]) {
+fn sort_markets_by_oi_volume(markets: &mut [Market]) {
     markets.sort_by(|a, b| {
-        let a_oi = get_i64(a, "open_interest").unwrap_or(0);
-        let b_oi = get_i64(b, "open_interest").unwrap_or(0);
-        let a_vol = market_volume(a);
-        let b_vol = market_volume(b);
-        let a_vol24 = get_i64(a, "volume_24h").unwrap_or(0);
-        let b_vol24 = get_i64(b, "volume_24h").unwrap_or(0);
+        let a_oi = a.open_interest.unwrap_or(0);
+        let b_oi = b.open_interest.unwrap_or(0);
+        let a_vol = a.ranking_volume();
+        let b_vol = b.ranking_volume();
+        let a_vol24 = a.volume_24h.unwrap_or(0);
+        let b_vol24 = b.volume_24h.unwrap_or(0);
 
         b_oi.cmp(&a_oi)
             .then_with(|| b_vol.cmp(&a_vol))
@@ -217,443 +443,265 @@ fn sort_markets_by_oi_volume(markets: &mut [Value]) {
     });
 }
 
-fn market_volume(market: &Value) -> i64 {
-    get_i64(market, "open_volume")
-        .or_else(|| get_i64(market, "volume"))
-        .or_else(|| get_i64(market, "volume_24h"))
-        .unwrap_or(0)
-}
-
 fn upcoming_max_close_ts(days: i64) -> i64 {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
     let horizon_days = days.max(1);
-    now + horizon_days * 86_400
+    now_secs() + horizon_days * 86_400
 }
 
 async fn fetch_markets_universe(
     client: &KalshiClient,
     base_query: BTreeMap<String, String>,
     target_universe: usize,
-) -> anyhow::Result<Vec<Value>> {
-    let mut all_markets: Vec<Value> = Vec::with_capacity(target_universe.min(2000));
-    let mut cursor: Option<String> = None;
-    let page_size = 1000usize.min(target_universe.max(1));
-
-    loop {
-        let mut q = base_query.clone();
-        q.insert("limit".to_string(), page_size.to_string());
-        if let Some(c) = &cursor {
-            q.insert("cursor".to_string(), c.clone());
-        }
-
-        let data = client.get_public("/markets", Some(q)).await?;
-        let page = extract_array(&data, "markets");
-
-        if page.is_empty() {
-            break;
-        }
-        all_markets.extend(page);
-        if all_markets.len() >= target_universe {
-            break;
-        }
-
-        cursor = data
-            .get("cursor")
-            .and_then(Value::as_str)
-            .map(str::to_string)
-            .filter(|c| !c.is_empty());
-        if cursor.is_none() {
-            break;
-        }
-    }
-
-    all_markets.truncate(target_universe);
-    Ok(all_markets)
+) -> anyhow::Result<Vec<Market>> {
+    let opts = PageOpts {
+        page_size: None,
+        max_items: Some(target_universe),
+    };
+    client.collect_pages(base_query, opts).await
 }
 
 async fn fetch_open_markets_universe(
     client: &KalshiClient,
     days: Option<i64>,
-) -> anyhow::Result<Vec<Value>> {
-    let mut all_markets: Vec<Value> = Vec::with_capacity(2000);
-    let mut cursor: Option<String> = None;
+) -> anyhow::Result<Vec<Market>> {
     let max_close_ts = days.map(|n| upcoming_max_close_ts(n.max(1)));
-
-    loop {
-        let q = QueryParams::new()
-            .insert("status", "open")
-            .optional("max_close_ts", max_close_ts)
-            .limit(SEARCH_PAGE_SIZE)
-            .optional("cursor", cursor.as_deref())
-            .build_always();
-        let data = client.get_public("/markets", Some(q)).await?;
-        let page = extract_array(&data, "markets");
-
-        if page.is_empty() {
-            break;
-        }
-        all_markets.extend(page);
-
-        cursor = data
-            .get("cursor")
-            .and_then(Value::as_str)
-            .map(str::to_string)
-            .filter(|c| !c.is_empty());
-        if cursor.is_none() {
-            break;
-        }
-    }
-
-    Ok(all_markets)
+    let q = QueryParams::new()
+        .insert("status", "open")
+        .optional("max_close_ts", max_close_ts)
+        .build_always();
+    let opts = PageOpts {
+        page_size: Some(SEARCH_PAGE_SIZE),
+        max_items: None,
+    };
+    client.collect_pages(q, opts).await
 }
 
 async fn search_open_markets_in_series(
     client: &KalshiClient,
     series_ticker: &str,
-    query: &FuzzyQuery,
-) -> anyhow::Result<Vec<Value>> {
-    let mut events: Vec<Value> = Vec::with_capacity(256);
-    let mut cursor: Option<String> = None;
-
-    loop {
-        let q = QueryParams::new()
-            .insert("series_ticker", series_ticker)
-            .insert("status", "open")
-            .insert("with_nested_markets", "true")
-            .limit(200)
-            .optional("cursor", cursor.as_deref())
-            .build_always();
-        let data = client.get_public("/events", Some(q)).await?;
-        let page = extract_array(&data, "events");
-        if page.is_empty() {
-            break;
-        }
-        events.extend(page);
-
-        cursor = data
-            .get("cursor")
-            .and_then(Value::as_str)
-            .map(str::to_string)
-            .filter(|c| !c.is_empty());
-        if cursor.is_none() {
-            break;
-        }
-    }
+    query: &SearchQuery,
+) -> anyhow::Result<Vec<Market>> {
+    let q = QueryParams::new()
+        .insert("series_ticker", series_ticker)
+        .insert("status", "open")
+        .insert("with_nested_markets", "true")
+        .build_always();
+    let events: Vec<Event> = client.collect_pages(q, PageOpts::default()).await?;
+    Ok(score_series_events(events, query))
+}
 
+/// Active markets of `events` (with nested markets) matching `query`.
+fn score_series_events(events: Vec<Event>, query: &SearchQuery) -> Vec<Market> {
     let mut results = Vec::new();
 
-    for event in events {
-        let event_match = row_matches(
-            &event,
-            &["ticker", "event_ticker", "series_ticker", "title", "sub_title"],
-            query,
-        );
-        let markets = extract_array(&event, "markets");
+    for mut event in events {
+        // Markets of a matching event match too, scored at least as the event.
+        let event_score = query.score(&[
+            (
+                FieldKind::Ticker,
+                event.extra.get("ticker").and_then(|v| v.as_str()),
+            ),
+            (FieldKind::Ticker, Some(&event.event_ticker)),
+            (FieldKind::Ticker, event.series_ticker.as_deref()),
+            (FieldKind::Title, event.title.as_deref()),
+            (FieldKind::Subtitle, event.sub_title.as_deref()),
+        ]);
+        let markets = event.markets.take().unwrap_or_default();
         let event_market_count = markets.len() as i64;
 
         for mut market in markets {
-            let is_active = market
-                .get("status")
-                .and_then(Value::as_str)
-                .map(is_active_market_status)
-                .unwrap_or(true);
-            if !is_active {
+            if !is_active_market(&market) {
                 continue;
             }
 
-            let market_match = row_matches(
-                &market,
-                &[
-                    "ticker",
-                    "event_ticker",
-                    "title",
-                    "yes_sub_title",
-                    "no_sub_title",
-                    "subtitle",
-                ],
-                query,
-            );
-
-            if !(event_match || market_match) {
-                continue;
-            }
+            let market_score = query.score(&market_search_fields(&market));
+            let score = match (market_score, event_score) {
+                (Some(m), Some(e)) => m.max(e),
+                (Some(score), None) | (None, Some(score)) => score,
+                (None, None) => continue,
+            };
 
-            if let Some(obj) = market.as_object_mut() {
-                obj.insert(
-                    "event_market_count".to_string(),
-                    Value::from(event_market_count),
-                );
-            }
+            market.event_market_count = Some(event_market_count);
+            market.search_score = Some(score);
             results.push(market);
         }
     }
 
-    Ok(results)
+    results
 }
 
 async fn search_open_markets(
     client: &KalshiClient,
     query: &str,
+    search: &SearchQuery,
     days: Option<i64>,
-) -> anyhow::Result<Vec<Value>> {
-    let fuzzy = FuzzyQuery::new(query);
-    if is_ticker_like_query(query) {
+) -> anyhow::Result<Vec<Market>> {
+    if search.is_ticker_like() {
         let series_results =
-            search_open_markets_in_series(client, &query.to_ascii_uppercase(), &fuzzy).await?;
+            search_open_markets_in_series(client, &query.trim().to_ascii_uppercase(), search)
+                .await?;
         if !series_results.is_empty() {
             return Ok(series_results);
         }
     }
-    let mut markets = fetch_open_markets_universe(client, days).await?;
-
-    markets.retain(|market| {
-        let is_active = market
-            .get("status")
-            .and_then(Value::as_str)
-            .map(is_active_market_status)
-            .unwrap_or(true);
-        if !is_active {
-            return false;
-        }
-
-        row_matches(
-            market,
-            &[
-                "ticker",
-                "event_ticker",
-                "title",
-                "yes_sub_title",
-                "no_sub_title",
-                "subtitle",
-            ],
-            &fuzzy,
-        )
-    });
-
-    Ok(markets)
-}
-
-fn row_matches(row: &Value, keys: &[&str], query: &FuzzyQuery) -> bool {
-    let combined = keys
-        .iter()
-        .filter_map(|key| row.get(key).and_then(Value::as_str))
-        .collect::<Vec<_>>()
-        .join(" ");
-    fuzzy_contains(&combined, query)
-}
-
-fn is_active_market_status(status: &str) -> bool {
-    status.eq_ignore_ascii_case("active") || status.eq_ignore_ascii_case("open")
-}
-
-fn is_ticker_like_query(query: &str) -> bool {
-    !query.is_empty()
-        && !query.chars().any(char::is_whitespace)
-        && query
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
-}
-
-impl FuzzyQuery {
-    fn new(query: &str) -> Self {
-        let normalized = normalize_for_match(query);
-        let tokens = normalized
-            .split_whitespace()
-            .map(str::to_string)
-            .collect::<Vec<_>>();
-        Self { normalized, tokens }
-    }
+    let markets = fetch_open_markets_universe(client, days).await?;
+
+    Ok(markets
+        .into_iter()
+        .filter(is_active_market)
+        .filter_map(|mut market| {
+            market.search_score = Some(search.score(&market_search_fields(&market))?);
+            Some(market)
+        })
+        .collect())
 }
 
-fn fuzzy_contains(value: &str, query: &FuzzyQuery) -> bool {
-    let value_norm = normalize_for_match(value);
-    if value_norm.is_empty() || query.normalized.is_empty() {
-        return false;
-    }
-    if value_norm.contains(&query.normalized) {
-        return true;
-    }
-
-    let value_tokens = value_norm.split_whitespace().collect::<Vec<_>>();
-    let matched_flags = query
-        .tokens
-        .iter()
-        .map(|token| token_matches(token, &value_tokens, &value_norm))
-        .collect::<Vec<_>>();
-    let matched = matched_flags.iter().filter(|matched| **matched).count();
-
-    if query.tokens.len() >= 3 {
-        let has_significant = query.tokens.iter().any(|token| !is_location_like_token(token));
-        let significant_matched = query
-            .tokens
-            .iter()
-            .zip(matched_flags.iter())
-            .any(|(token, matched)| !is_location_like_token(token) && *matched);
-        if has_significant && !significant_matched {
-            return false;
+/// The local market index, or `None` to search the API. A stale index is
+/// still used while a background sync refreshes it for the next search.
+fn load_search_index(ctx: &AppContext) -> Option<MarketIndex> {
+    let index = match MarketIndex::load(&ctx.runtime) {
+        Ok(index) => index?,
+        Err(err) => {
+            eprintln!("warning: {err:#}; searching the API instead");
+            return None;
         }
+    };
+    if let Some(err) = SyncError::load(&ctx.runtime) {
+        eprintln!(
+            "warning: market index sync failed {}m ago: {}",
+            err.age_secs() / 60,
+            err.message
+        );
     }
-
-    matched >= required_token_matches(query.tokens.len())
-}
-
-fn token_matches(query_token: &str, value_tokens: &[&str], value_norm: &str) -> bool {
-    if query_token.len() <= 2 {
-        return value_norm.contains(query_token);
+    let refresh_after = ctx.runtime.preferences.index_refresh_after_secs();
+    if refresh_after.is_some_and(|max| index.age_secs() > max)
+        && matches!(spawn_background_sync(&ctx.runtime), Ok(true))
+    {
+        eprintln!(
+            "note: market index is {}m old; refreshing it in the background",
+            index.age_secs() / 60
+        );
     }
-
-    let mut candidates = vec![query_token.to_string()];
-    candidates.extend(token_aliases(query_token).iter().map(|token| token.to_string()));
-
-    candidates.iter().any(|candidate| {
-        if candidate.contains(' ') {
-            value_norm.contains(candidate)
-        } else {
-            value_tokens.iter().any(|value_token| {
-                value_token.contains(candidate.as_str())
-                    || (candidate.len() >= 5
-                        && value_token.len() >= 5
-                        && edit_distance_at_most(value_token, candidate, 1))
-            })
-        }
-    })
+    Some(index)
 }
 
-fn required_token_matches(token_count: usize) -> usize {
-    match token_count {
-        0 => 0,
-        1 => 1,
-        2 => 2,
-        3 => 3,
-        _ => token_count - 1,
+/// Same search as [`search_open_markets`] over the local index.
+fn search_market_index(
+    index: &MarketIndex,
+    query: &str,
+    search: &SearchQuery,
+    days: Option<i64>,
+) -> Vec<Market> {
+    let events = index.events_by_ticker();
+    if search.is_ticker_like() {
+        let series_ticker = query.trim().to_ascii_uppercase();
+        let mut series_events: BTreeMap<&str, Event> = BTreeMap::new();
+        for market in &index.markets {
+            let Some(event) = market
+                .event_ticker
+                .as_deref()
+                .and_then(|t| events.get(t))
+                .filter(|e| e.series_ticker.as_deref() == Some(series_ticker.as_str()))
+            else {
+                continue;
+            };
+            series_events
+                .entry(event.event_ticker.as_str())
+                .or_insert_with(|| (*event).clone())
+                .markets
+                .get_or_insert_with(Vec::new)
+                .push(market.clone());
+        }
+        let series_results = score_series_events(series_events.into_values().collect(), search);
+        if !series_results.is_empty() {
+            return series_results;
+        }
     }
-}
 
-fn is_location_like_token(token: &str) -> bool {
-    matches!(
-        token,
-        "new"
-            | "york"
-            | "city"
-            | "los"
-            | "san"
-            | "las"
-            | "st"
-            | "saint"
-            | "at"
-            | "in"
-            | "on"
-            | "vs"
-            | "the"
-    )
+    let max_close_ts = days.map(upcoming_max_close_ts);
+    let counts = index.event_market_counts();
+    index
+        .markets
+        .iter()
+        .filter(|m| is_active_market(m))
+        .filter(|m| max_close_ts.is_none_or(|max| m.close_ts().is_none_or(|close| close <= max)))
+        .filter_map(|market| {
+            let score = search.score(&market_search_fields(market))?;
+            let mut market = market.clone();
+            market.event_market_count = market
+                .event_ticker
+                .as_deref()
+                .and_then(|t| counts.get(t).copied());
+            market.search_score = Some(score);
+            Some(market)
+        })
+        .collect()
 }
 
-fn token_aliases(token: &str) -> &'static [&'static str] {
-    match token {
-        "basketball" => &["nba", "wnba", "nbagame", "kxnbagame", "kxwnbagame"],
-        "football" => &["nfl", "ncaaf"],
-        "baseball" => &["mlb"],
-        "hockey" => &["nhl"],
-        "soccer" => &["mls", "epl", "fifa"],
-        "knicks" => &["nyk"],
-        _ => &[],
-    }
+fn market_search_fields(market: &Market) -> [(FieldKind, Option<&str>); 6] {
+    [
+        (FieldKind::Ticker, Some(&market.ticker)),
+        (FieldKind::Ticker, market.event_ticker.as_deref()),
+        (FieldKind::Title, market.title.as_deref()),
+        (FieldKind::Subtitle, market.yes_sub_title.as_deref()),
+        (FieldKind::Subtitle, market.no_sub_title.as_deref()),
+        (FieldKind::Subtitle, market.subtitle.as_deref()),
+    ]
 }
 
-fn normalize_for_match(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| {
-            if c.is_ascii_alphanumeric() {
-                c.to_ascii_lowercase()
-            } else {
-                ' '
-            }
-        })
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+fn is_active_market(market: &Market) -> bool {
+    market
+        .status
+        .as_deref()
+        .map(is_active_market_status)
+        .unwrap_or(true)
 }
 
-fn edit_distance_at_most(a: &str, b: &str, max: usize) -> bool {
-    let a_chars = a.chars().collect::<Vec<_>>();
-    let b_chars = b.chars().collect::<Vec<_>>();
-    let alen = a_chars.len();
-    let blen = b_chars.len();
-    if alen.abs_diff(blen) > max {
-        return false;
-    }
-
-    let mut prev = (0..=blen).collect::<Vec<_>>();
-    let mut curr = vec![0; blen + 1];
-
-    for i in 1..=alen {
-        curr[0] = i;
-        let mut row_min = curr[0];
-        for j in 1..=blen {
-            let cost = usize::from(a_chars[i - 1] != b_chars[j - 1]);
-            let del = prev[j] + 1;
-            let ins = curr[j - 1] + 1;
-            let sub = prev[j - 1] + cost;
-            curr[j] = del.min(ins).min(sub);
-            row_min = row_min.min(curr[j]);
-        }
-        if row_min > max {
-            return false;
-        }
-        std::mem::swap(&mut prev, &mut curr);
-    }
-
-    prev[blen] <= max
+fn is_active_market_status(status: &str) -> bool {
+    status.eq_ignore_ascii_case("active") || status.eq_ignore_ascii_case("open")
 }
 
 async fn enrich_event_market_counts(
     client: &KalshiClient,
-    markets: &mut [Value],
+    markets: &mut [Market],
 ) -> anyhow::Result<()> {
     let mut counts: HashMap<String, i64> = HashMap::new();
 
     for market in markets.iter_mut() {
-        let event_ticker = market
-            .get("event_ticker")
-            .and_then(Value::as_str)
-            .map(str::to_string);
-
-        let Some(event_ticker) = event_ticker else {
+        let Some(event_ticker) = market.event_ticker.clone() else {
             continue;
         };
 
         let count = if let Some(cached) = counts.get(&event_ticker) {
             *cached
         } else {
-            let mut q = BTreeMap::new();
-            q.insert("with_nested_markets".to_string(), "true".to_string());
-            let data = client
-                .get_public(&format!("/events/{event_ticker}"), Some(q))
-                .await?;
-            let cnt = data
-                .get("event")
-                .and_then(|e| e.get("markets"))
-                .and_then(Value::as_array)
-                .map(|a| a.len() as i64)
-                .or_else(|| {
-                    data.get("markets")
-                        .and_then(Value::as_array)
-                        .map(|a| a.len() as i64)
-                })
-                .unwrap_or(0);
+            let detail = client.get_event(&event_ticker, true).await?;
+            let cnt = detail.market_count().unwrap_or(0) as i64;
             counts.insert(event_ticker.clone(), cnt);
             cnt
         };
 
-        if let Some(obj) = market.as_object_mut() {
-            obj.insert("event_market_count".to_string(), Value::from(count));
-        }
+        market.event_market_count = Some(count);
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_time_arg;
+
+    #[test]
+    fn parses_times_and_rejects_overflowing_ages() {
+        assert_eq!(parse_time_arg("1700000000"), Ok(1_700_000_000));
+        assert_eq!(parse_time_arg("2023-11-14"), Ok(1_699_920_000));
+        assert!(parse_time_arg("2h").unwrap() < parse_time_arg("now").unwrap());
+        assert!(parse_time_arg("3y").unwrap_err().contains("unknown unit"));
+        assert!(parse_time_arg(&format!("{}w", i64::MAX))
+            .unwrap_err()
+            .contains("out of range"));
+        assert!(parse_time_arg(&format!("{}d", i64::MIN / 2))
+            .unwrap_err()
+            .contains("out of range"));
+    }
+}
@@ -1,9 +1,76 @@
+pub mod apikeys;
 pub mod config_cmd;
 pub mod events;
 pub mod exchange;
+pub mod index_cmd;
 pub mod markets;
 pub mod order;
 pub mod portfolio;
+pub mod search_aliases;
+pub mod series;
 pub mod shell;
 pub mod trades;
 pub mod watch;
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::client::PageOpts;
+use crate::config::Preferences;
+use crate::filter::{apply_rows, Filter, SortKeys};
+
+/// Pagination flags shared by list commands.
+#[derive(Debug, Clone, Args)]
+#[command(about = None, long_about = None)]
+pub struct PageArgs {
+    /// Follow pagination cursors until every row is fetched
+    #[arg(long, conflicts_with = "max")]
+    all: bool,
+    /// Follow pagination cursors until this many rows are fetched
+    #[arg(long)]
+    max: Option<usize>,
+}
+
+impl PageArgs {
+    /// Without `--all`/`--max` a single page is fetched, sized by `limit`
+    /// (the command's `--limit`), else the profile's `limit` preference,
+    /// else `default`.
+    pub fn opts(&self, prefs: &Preferences, limit: Option<usize>, default: usize) -> PageOpts {
+        let limit = prefs.limit_or(limit, default);
+        if self.all {
+            PageOpts::default()
+        } else if let Some(max) = self.max {
+            PageOpts {
+                page_size: None,
+                max_items: Some(max),
+            }
+        } else {
+            PageOpts {
+                page_size: Some(limit),
+                max_items: Some(limit),
+            }
+        }
+    }
+}
+
+/// `--filter`/`--sort` flags shared by list commands, applied to the rows
+/// as they appear in `-o json`.
+#[derive(Debug, Clone, Args)]
+#[command(about = None, long_about = None)]
+pub struct RowArgs {
+    /// Keep rows matching an expression over their JSON fields,
+    /// e.g. 'yes_ask < 20 && volume_24h > 1000'
+    #[arg(long, value_parser = Filter::parse)]
+    filter: Option<Filter>,
+    /// Sort by comma-separated JSON fields, `-` for descending,
+    /// e.g. -open_interest,close_time
+    #[arg(long, allow_hyphen_values = true, value_parser = SortKeys::parse)]
+    sort: Option<SortKeys>,
+}
+
+impl RowArgs {
+    /// Filters, then sorts; the command's own order breaks ties.
+    pub fn apply<T: Serialize>(&self, rows: &mut Vec<T>) -> anyhow::Result<()> {
+        apply_rows(rows, self.filter.as_ref(), self.sort.as_ref())
+    }
+}
@@ -1,12 +1,15 @@
 use std::fmt;
 
-use clap::{Args, Subcommand, ValueEnum};
+use clap::{ArgAction, Args, Subcommand, ValueEnum};
 use serde_json::json;
 
+use super::{PageArgs, RowArgs};
 use crate::{
-    client::KalshiClient,
+    client::{KalshiClient, PageOpts},
     config::ensure_auth,
-    output::{extract_array, print_value, render_order_table},
+    index::MarketIndex,
+    models::{AmendOrderRequest, CreateOrderRequest, Order},
+    output::{print_value, render_order_table},
     query::QueryParams,
     AppContext,
 };
@@ -117,6 +120,9 @@ enum OrderSubcmd {
         order_type: OrderType,
         #[arg(long, default_value = "gtc")]
         tif: Tif,
+        /// Idempotency key for the order; a random UUID is generated when omitted
+        #[arg(long)]
+        client_order_id: Option<String>,
     },
     Cancel {
         order_id: String,
@@ -137,8 +143,13 @@ enum OrderSubcmd {
         ticker: Option<String>,
         #[arg(long)]
         status: Option<OrderStatus>,
-        #[arg(long, default_value_t = true)]
-        compact: bool,
+        /// Compact table [default: true, or the profile's `compact` preference]
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", action = ArgAction::Set)]
+        compact: Option<bool>,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Get {
         order_id: String,
@@ -158,30 +169,28 @@ pub async fn run(ctx: &AppContext, cmd: OrderCmd) -> anyhow::Result<()> {
             price,
             order_type,
             tif,
+            client_order_id,
         } => {
-            let mut body = json!({
-                "ticker": ticker,
-                "side": side.to_string(),
-                "action": action.to_string(),
-                "count": count,
-                "type": order_type.to_string(),
-                "time_in_force": tif.to_string(),
-            });
-
-            if matches!(side, Side::Yes) {
-                body["yes_price"] = json!(price);
-            } else {
-                body["no_price"] = json!(price);
-            }
+            warn_unknown_ticker(ctx, &ticker);
+            let is_yes = matches!(side, Side::Yes);
+            let request = CreateOrderRequest {
+                ticker,
+                side: side.to_string(),
+                action: action.to_string(),
+                count,
+                order_type: order_type.to_string(),
+                time_in_force: tif.to_string(),
+                yes_price: is_yes.then_some(price),
+                no_price: (!is_yes).then_some(price),
+                client_order_id,
+            };
 
-            let data = client.post_auth("/portfolio/orders", Some(body)).await?;
-            print_value(ctx.output_mode, &data)
+            let order = client.create_order(&request).await?;
+            print_value(ctx.output_mode, &ctx.display, &json!({ "order": order }))
         }
         OrderSubcmd::Cancel { order_id } => {
-            let data = client
-                .delete_auth(&format!("/portfolio/orders/{order_id}"), None)
-                .await?;
-            print_value(ctx.output_mode, &data)
+            let data = client.cancel_order(&order_id).await?;
+            print_value(ctx.output_mode, &ctx.display, &data)
         }
         OrderSubcmd::CancelAll { ticker } => {
             let q = QueryParams::new()
@@ -189,31 +198,22 @@ pub async fn run(ctx: &AppContext, cmd: OrderCmd) -> anyhow::Result<()> {
                 .optional("ticker", ticker)
                 .build_always();
 
-            let existing = client.get_auth("/portfolio/orders", Some(q)).await?;
+            let existing: Vec<Order> = client.collect_pages(q, PageOpts::default()).await?;
             let ids: Vec<String> = existing
-                .get("orders")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|o| {
-                            o.get("order_id")
-                                .and_then(|v| v.as_str())
-                                .map(str::to_string)
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
+                .into_iter()
+                .map(|o| o.into_inner().order_id)
+                .filter(|id| !id.is_empty())
+                .collect();
 
             if ids.is_empty() {
                 print_value(
                     ctx.output_mode,
+                    &ctx.display,
                     &json!({"canceled": 0, "message": "No resting orders"}),
                 )
             } else {
-                let data = client
-                    .delete_auth("/portfolio/orders/batched", Some(json!({"ids": ids})))
-                    .await?;
-                print_value(ctx.output_mode, &data)
+                let orders = client.batch_cancel_orders(&ids).await?;
+                print_value(ctx.output_mode, &ctx.display, &json!({ "orders": orders }))
             }
         }
         OrderSubcmd::Amend {
@@ -221,38 +221,59 @@ pub async fn run(ctx: &AppContext, cmd: OrderCmd) -> anyhow::Result<()> {
             price,
             count,
         } => {
-            let mut body = json!({});
-            if let Some(p) = price {
-                body["yes_price"] = json!(p);
-            }
-            if let Some(c) = count {
-                body["count"] = json!(c);
-            }
-            let data = client
-                .post_auth(&format!("/portfolio/orders/{order_id}/amend"), Some(body))
-                .await?;
-            print_value(ctx.output_mode, &data)
+            let amend = AmendOrderRequest {
+                yes_price: price,
+                count,
+            };
+            let data = client.amend_order(&order_id, &amend).await?;
+            print_value(ctx.output_mode, &ctx.display, &data)
         }
         OrderSubcmd::List {
             ticker,
             status,
             compact,
+            page,
+            rows,
         } => {
             let q = QueryParams::new()
-                .limit(200)
                 .optional("ticker", ticker)
                 .optional("status", status.map(|s| s.to_string()))
-                .build();
+                .build_always();
 
-            let data = client.get_auth("/portfolio/orders", q).await?;
-            let rows = extract_array(&data, "orders");
-            render_order_table(ctx.output_mode, &rows, compact)
+            let prefs = &ctx.runtime.preferences;
+            let mut orders = client.collect_pages(q, page.opts(prefs, None, 200)).await?;
+            rows.apply(&mut orders)?;
+            render_order_table(
+                ctx.output_mode,
+                &ctx.display,
+                &orders,
+                prefs.compact_or(compact),
+            )
         }
         OrderSubcmd::Get { order_id } => {
-            let data = client
-                .get_auth(&format!("/portfolio/orders/{order_id}"), None)
-                .await?;
-            print_value(ctx.output_mode, &data)
+            let order = client.get_order(&order_id).await?;
+            print_value(ctx.output_mode, &ctx.display, &json!({ "order": order }))
         }
     }
 }
+
+/// Flags a likely typo before the order goes out. Only a warning: markets
+/// listed since the last index sync are legitimately missing from it.
+fn warn_unknown_ticker(ctx: &AppContext, ticker: &str) {
+    let Ok(Some(index)) = MarketIndex::load(&ctx.runtime) else {
+        return;
+    };
+    if index.market(ticker).is_some() {
+        return;
+    }
+    let suggestions = index.suggest(ticker, 3);
+    eprintln!(
+        "warning: {ticker} is not an open market in the local index (synced {}m ago){}",
+        index.age_secs() / 60,
+        if suggestions.is_empty() {
+            String::new()
+        } else {
+            format!("; did you mean {}?", suggestions.join(", "))
+        }
+    );
+}
@@ -1,11 +1,14 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-
-use clap::{Args, Subcommand};
+use clap::{ArgAction, Args, Subcommand};
 
+use super::{PageArgs, RowArgs};
 use crate::{
     client::KalshiClient,
+    clock::now_secs,
     config::ensure_auth,
-    output::{extract_array, print_rows, render_balance_table, render_positions_table},
+    models::{Fill, Position, Settlement},
+    output::{
+        render_balance_table, render_fills_table, render_positions_table, render_settlements_table,
+    },
     query::QueryParams,
     AppContext,
 };
@@ -28,31 +31,43 @@ enum PortfolioSubcmd {
         settled: bool,
         #[arg(long, default_value_t = false)]
         unsettled: bool,
-        #[arg(long, default_value_t = true)]
-        compact: bool,
+        /// Compact table [default: true, or the profile's `compact` preference]
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", action = ArgAction::Set)]
+        compact: Option<bool>,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Fills {
         #[arg(long)]
         ticker: Option<String>,
         #[arg(long, default_value_t = 7)]
         days: u64,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        rows: RowArgs,
     },
     Settlements {
         #[arg(long)]
         ticker: Option<String>,
         #[arg(long, default_value_t = 30)]
         days: u64,
+        #[command(flatten)]
+        page: PageArgs,
     },
 }
 
 pub async fn run(ctx: &AppContext, cmd: PortfolioCmd) -> anyhow::Result<()> {
     ensure_auth(&ctx.runtime)?;
     let client = KalshiClient::new(ctx.runtime.clone())?;
+    let prefs = &ctx.runtime.preferences;
 
     match cmd.command {
         PortfolioSubcmd::Balance => {
-            let data = client.get_auth("/portfolio/balance", None).await?;
-            render_balance_table(ctx.output_mode, &data)
+            let balance = client.get_balance().await?;
+            render_balance_table(ctx.output_mode, &ctx.display, &balance)
         }
         PortfolioSubcmd::Positions {
             ticker,
@@ -60,6 +75,8 @@ pub async fn run(ctx: &AppContext, cmd: PortfolioCmd) -> anyhow::Result<()> {
             settled,
             unsettled,
             compact,
+            page,
+            rows,
         } => {
             let settlement_status = if settled {
                 Some("settled")
@@ -69,68 +86,49 @@ pub async fn run(ctx: &AppContext, cmd: PortfolioCmd) -> anyhow::Result<()> {
                 None
             };
             let q = QueryParams::new()
-                .limit(200)
                 .optional("ticker", ticker)
                 .optional("event_ticker", event_ticker)
                 .optional("settlement_status", settlement_status)
-                .build();
+                .build_always();
 
-            let data = client.get_auth("/portfolio/positions", q).await?;
-            let rows = extract_array(&data, "market_positions");
-            render_positions_table(ctx.output_mode, &rows, compact)
+            let mut positions: Vec<Position> =
+                client.collect_pages(q, page.opts(prefs, None, 200)).await?;
+            rows.apply(&mut positions)?;
+            render_positions_table(
+                ctx.output_mode,
+                &ctx.display,
+                &positions,
+                prefs.compact_or(compact),
+            )
         }
-        PortfolioSubcmd::Fills { ticker, days } => {
+        PortfolioSubcmd::Fills {
+            ticker,
+            days,
+            page,
+            rows,
+        } => {
             let q = QueryParams::new()
-                .limit(200)
                 .insert("min_ts", ts_days_ago(days))
                 .optional("ticker", ticker)
-                .build();
+                .build_always();
 
-            let data = client.get_auth("/portfolio/fills", q).await?;
-            let rows = extract_array(&data, "fills");
-            print_rows(
-                ctx.output_mode,
-                &rows,
-                &[
-                    "trade_id",
-                    "ticker",
-                    "side",
-                    "action",
-                    "count",
-                    "yes_price",
-                    "created_time",
-                ],
-            )
+            let mut fills: Vec<Fill> = client.collect_pages(q, page.opts(prefs, None, 200)).await?;
+            rows.apply(&mut fills)?;
+            render_fills_table(ctx.output_mode, &ctx.display, &fills)
         }
-        PortfolioSubcmd::Settlements { ticker, days } => {
+        PortfolioSubcmd::Settlements { ticker, days, page } => {
             let q = QueryParams::new()
-                .limit(200)
                 .insert("min_ts", ts_days_ago(days))
                 .optional("ticker", ticker)
-                .build();
+                .build_always();
 
-            let data = client.get_auth("/portfolio/settlements", q).await?;
-            let rows = extract_array(&data, "settlements");
-            print_rows(
-                ctx.output_mode,
-                &rows,
-                &[
-                    "ticker",
-                    "market_result",
-                    "realized_pnl",
-                    "yes_count",
-                    "no_count",
-                    "settlement_ts",
-                ],
-            )
+            let settlements: Vec<Settlement> =
+                client.collect_pages(q, page.opts(prefs, None, 200)).await?;
+            render_settlements_table(ctx.output_mode, &ctx.display, &settlements)
         }
     }
 }
 
 fn ts_days_ago(days: u64) -> i64 {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
-    now - (days as i64 * 86_400)
+    now_secs() - (days as i64 * 86_400)
 }
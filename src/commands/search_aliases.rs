@@ -0,0 +1,123 @@
+use clap::{Args, Subcommand};
+use serde_json::json;
+
+use crate::{
+    config::search_aliases_path,
+    output::{left, print_value, standard_table, DisplayOptions, OutputMode},
+    search::{normalize_term, SearchAliases},
+};
+
+#[derive(Debug, Clone, Args)]
+pub struct SearchAliasesCmd {
+    #[command(subcommand)]
+    command: SearchAliasesSubcmd,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum SearchAliasesSubcmd {
+    /// Make `markets search` for TERM also match ALIASES
+    Add {
+        term: String,
+        #[arg(required_unless_present = "stop_word", conflicts_with = "stop_word")]
+        aliases: Vec<String>,
+        /// Add TERM as a stop-word instead: a long query matching only
+        /// stop-words does not match
+        #[arg(long)]
+        stop_word: bool,
+    },
+    /// Remove ALIASES of TERM, or all of its aliases when none are given
+    Remove {
+        term: String,
+        #[arg(conflicts_with = "stop_word")]
+        aliases: Vec<String>,
+        /// Remove TERM from the stop-words instead
+        #[arg(long)]
+        stop_word: bool,
+    },
+    /// Show built-in and user aliases and stop-words
+    List,
+}
+
+pub async fn run(
+    cmd: SearchAliasesCmd,
+    mode: OutputMode,
+    display: &DisplayOptions,
+) -> anyhow::Result<()> {
+    let mut user = SearchAliases::load()?;
+
+    match cmd.command {
+        SearchAliasesSubcmd::Add {
+            term,
+            aliases,
+            stop_word,
+        } => {
+            let term = normalize_term(&term)?;
+            let result = if stop_word {
+                json!({"ok": true, "stop_word": term, "added": user.add_stop_word(&term)?})
+            } else {
+                json!({"ok": true, "term": term, "added": user.add(&term, &aliases)?})
+            };
+            user.save()?;
+            print_value(mode, display, &result)
+        }
+        SearchAliasesSubcmd::Remove {
+            term,
+            aliases,
+            stop_word,
+        } => {
+            let term = normalize_term(&term)?;
+            let result = if stop_word {
+                json!({"ok": true, "stop_word": term, "removed": user.remove_stop_word(&term)?})
+            } else {
+                json!({"ok": true, "term": term, "removed": user.remove(&term, &aliases)?})
+            };
+            user.save()?;
+            print_value(mode, display, &result)
+        }
+        SearchAliasesSubcmd::List => list(mode, display, &user),
+    }
+}
+
+fn list(mode: OutputMode, display: &DisplayOptions, user: &SearchAliases) -> anyhow::Result<()> {
+    let source = |built_in: bool| if built_in { "built-in" } else { "user" };
+    let aliases = user.entries();
+    let stop_words = user.all_stop_words();
+
+    if mode == OutputMode::Json {
+        return print_value(
+            mode,
+            display,
+            &json!({
+                "path": search_aliases_path()?,
+                "aliases": aliases
+                    .iter()
+                    .map(|(term, aliases, built_in)| {
+                        json!({"term": term, "aliases": aliases, "source": source(*built_in)})
+                    })
+                    .collect::<Vec<_>>(),
+                "stop_words": stop_words
+                    .iter()
+                    .map(|(word, built_in)| json!({"word": word, "source": source(*built_in)}))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+    }
+
+    let mut table = standard_table(display, &["Term", "Aliases", "Source"]);
+    for (term, aliases, built_in) in &aliases {
+        table.add_row(vec![
+            left(term.as_str()),
+            left(aliases.join(", ")),
+            left(source(*built_in)),
+        ]);
+    }
+    println!("{table}");
+
+    let mut table = standard_table(display, &["Stop-word", "Source"]);
+    for (word, built_in) in &stop_words {
+        table.add_row(vec![left(word.as_str()), left(source(*built_in))]);
+    }
+    println!("{table}");
+    println!("User file: {}", search_aliases_path()?.display());
+    Ok(())
+}
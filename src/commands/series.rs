@@ -0,0 +1,94 @@
+use clap::{ArgAction, Args, Subcommand};
+
+use crate::{
+    client::{KalshiClient, PageOpts},
+    clock::now_secs,
+    models::Event,
+    output::{render_series_detail, render_series_next, render_series_table},
+    query::QueryParams,
+    AppContext,
+};
+
+#[derive(Debug, Clone, Args)]
+pub struct SeriesCmd {
+    #[command(subcommand)]
+    command: SeriesSubcmd,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum SeriesSubcmd {
+    List {
+        #[arg(long)]
+        category: Option<String>,
+        /// Only series with any of these tags (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+    /// Frequency, settlement sources and fee schedule of a series
+    Get { series: String },
+    /// The next open event of a recurring series and its markets
+    Next {
+        series: String,
+        /// Compact table [default: true, or the profile's `compact` preference]
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", action = ArgAction::Set)]
+        compact: Option<bool>,
+    },
+}
+
+pub async fn run(ctx: &AppContext, cmd: SeriesCmd) -> anyhow::Result<()> {
+    let client = KalshiClient::new(ctx.runtime.clone())?;
+
+    match cmd.command {
+        SeriesSubcmd::List { category, tags } => {
+            let tags = (!tags.is_empty()).then(|| tags.join(","));
+            let series = client
+                .list_series(category.as_deref(), tags.as_deref())
+                .await?;
+            render_series_table(ctx.output_mode, &ctx.display, &series)
+        }
+        SeriesSubcmd::Get { series } => {
+            let data = client.get_series(&series).await?;
+            render_series_detail(ctx.output_mode, &ctx.display, &data)
+        }
+        SeriesSubcmd::Next { series, compact } => {
+            let q = QueryParams::new()
+                .insert("series_ticker", &series)
+                .insert("status", "open")
+                .insert("with_nested_markets", "true")
+                .build_always();
+            let events: Vec<Event> = client.collect_pages(q, PageOpts::default()).await?;
+
+            let Some((mut event, close_ts)) = next_event(events, now_secs()) else {
+                anyhow::bail!("no open events in series {series}");
+            };
+            let mut markets = event.markets.take().unwrap_or_default();
+            markets.sort_by_key(|m| m.close_ts().unwrap_or(i64::MAX));
+            render_series_next(
+                ctx.output_mode,
+                &ctx.display,
+                &event,
+                &markets,
+                close_ts,
+                ctx.runtime.preferences.compact_or(compact),
+            )
+        }
+    }
+}
+
+/// The open event whose first market closes soonest (but not in the past),
+/// with that close time. Events without any known close time come last.
+fn next_event(events: Vec<Event>, now: i64) -> Option<(Event, Option<i64>)> {
+    events
+        .into_iter()
+        .map(|event| {
+            let close_ts = event
+                .markets
+                .iter()
+                .flatten()
+                .filter_map(|m| m.close_ts())
+                .filter(|ts| *ts >= now)
+                .min();
+            (event, close_ts)
+        })
+        .min_by_key(|(event, close_ts)| (close_ts.unwrap_or(i64::MAX), event.event_ticker.clone()))
+}
@@ -1,11 +1,28 @@
 use clap::Parser;
-use rustyline::DefaultEditor;
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
 
+use crate::config::RuntimeConfig;
+use crate::index::MarketIndex;
 use crate::output::OutputMode;
 use crate::{dispatch, Cli, Commands};
 
-pub async fn run(output_mode: OutputMode) -> anyhow::Result<()> {
-    let mut rl = DefaultEditor::new()?;
+/// Completions offered for one Tab press at most.
+const MAX_COMPLETIONS: usize = 50;
+
+pub async fn run(runtime: &RuntimeConfig, output_mode: OutputMode) -> anyhow::Result<()> {
+    let mut rl = Editor::<ShellHelper, DefaultHistory>::new()?;
+    // Tickers complete from the local market index, when there is one.
+    let tickers = MarketIndex::load(runtime)
+        .ok()
+        .flatten()
+        .map(|index| index.tickers())
+        .unwrap_or_default();
+    rl.set_helper(Some(ShellHelper { tickers }));
 
     println!("Kalshi interactive shell. Type 'help' for commands, 'exit' to quit.");
 
@@ -51,8 +68,8 @@ pub async fn run(output_mode: OutputMode) -> anyhow::Result<()> {
                             continue;
                         }
                         // Box::pin to avoid infinite-size future from recursion
-                        if let Err(err) = Box::pin(dispatch(cli)).await {
-                            eprintln!("error: {err}");
+                        if let Err(err) = Box::pin(dispatch(cli, Some(runtime))).await {
+                            eprintln!("error: {err:#}");
                         }
                     }
                     Err(e) => {
@@ -67,16 +84,84 @@ pub async fn run(output_mode: OutputMode) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Completes market, event and series tickers by prefix (case-insensitive).
+struct ShellHelper {
+    /// Sorted.
+    tickers: Vec<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = word_start(line, pos);
+        let word = line[start..pos].to_ascii_uppercase();
+        if word.is_empty() || word.starts_with('-') {
+            return Ok((start, Vec::new()));
+        }
+        let first = self.tickers.partition_point(|t| t.as_str() < word.as_str());
+        let candidates = self.tickers[first..]
+            .iter()
+            .take_while(|t| t.starts_with(&word))
+            .take(MAX_COMPLETIONS)
+            .cloned()
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+/// Byte offset of the word ending at `pos`; any Unicode whitespace separates words.
+fn word_start(line: &str, pos: usize) -> usize {
+    line[..pos]
+        .char_indices()
+        .rev()
+        .find(|(_, ch)| ch.is_whitespace())
+        .map_or(0, |(i, ch)| i + ch.len_utf8())
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
 fn print_shell_help() {
     println!("Commands:");
-    println!("  markets list|get|search|orderbook");
+    println!("  markets list|get|search|orderbook|candles");
     println!("  events list|get|top");
+    println!("  series list|get|next");
     println!("  order create|cancel|amend|list|get|cancel-all");
     println!("  portfolio balance|positions|fills|settlements");
     println!("  trades list");
     println!("  exchange status|schedule|announcements");
     println!("  watch ticker|orderbook|trades");
     println!("  config setup|show|path|reset");
+    println!("  index sync|status");
+    println!("  search-aliases add|remove|list");
     println!("  help    Show this help");
     println!("  exit    Quit the shell");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::word_start;
+
+    #[test]
+    fn finds_word_start_after_multi_byte_whitespace() {
+        assert_eq!(word_start("KXA", 3), 0);
+        assert_eq!(word_start("markets get KXA", 15), 12);
+        // U+3000 IDEOGRAPHIC SPACE is three bytes long.
+        let line = "markets\u{3000}KXA";
+        assert_eq!(&line[word_start(line, line.len())..], "KXA");
+        assert_eq!(word_start("get ", 4), 4);
+    }
+}
@@ -1,9 +1,8 @@
 use clap::{Args, Subcommand};
 
+use super::{PageArgs, RowArgs};
 use crate::{
-    client::KalshiClient,
-    output::{extract_array, print_rows},
-    query::QueryParams,
+    client::KalshiClient, models::Trade, output::render_trades_table, query::QueryParams,
     AppContext,
 };
 
@@ -18,8 +17,13 @@ enum TradesSubcmd {
     List {
         #[arg(long)]
         ticker: Option<String>,
-        #[arg(long, default_value_t = 50)]
-        limit: usize,
+        /// Rows to fetch [default: 50, or the profile's `limit` preference]
+        #[arg(long)]
+        limit: Option<usize>,
+        #[command(flatten)]
+        page: PageArgs,
+        #[command(flatten)]
+        rows: RowArgs,
     },
 }
 
@@ -27,18 +31,18 @@ pub async fn run(ctx: &AppContext, cmd: TradesCmd) -> anyhow::Result<()> {
     let client = KalshiClient::new(ctx.runtime.clone())?;
 
     match cmd.command {
-        TradesSubcmd::List { ticker, limit } => {
-            let q = QueryParams::new()
-                .limit(limit)
-                .optional("ticker", ticker)
-                .build();
-            let data = client.get_public("/markets/trades", q).await?;
-            let rows = extract_array(&data, "trades");
-            print_rows(
-                ctx.output_mode,
-                &rows,
-                &["trade_id", "ticker", "count", "yes_price", "created_time"],
-            )
+        TradesSubcmd::List {
+            ticker,
+            limit,
+            page,
+            rows,
+        } => {
+            let q = QueryParams::new().optional("ticker", ticker).build_always();
+            let mut trades: Vec<Trade> = client
+                .collect_pages(q, page.opts(&ctx.runtime.preferences, limit, 50))
+                .await?;
+            rows.apply(&mut trades)?;
+            render_trades_table(ctx.output_mode, &ctx.display, &trades)
         }
     }
 }
@@ -3,7 +3,9 @@ use futures_util::{SinkExt, StreamExt};
 use serde_json::{json, Value};
 use tokio_tungstenite::{connect_async, tungstenite::http::Request};
 
-use crate::{auth, config::ensure_auth, output::print_ndjson, output::OutputMode, AppContext};
+use crate::{
+    client::KalshiClient, config::ensure_auth, output::print_ndjson, output::OutputMode, AppContext,
+};
 
 #[derive(Debug, Clone, Args)]
 pub struct WatchCmd {
@@ -27,25 +29,16 @@ enum WatchSubcmd {
 }
 
 pub async fn run(ctx: &AppContext, cmd: WatchCmd) -> anyhow::Result<()> {
-    ensure_auth(&ctx.runtime)?;
-
-    let api_key = ctx
-        .runtime
-        .api_key
-        .clone()
-        .ok_or_else(|| anyhow::anyhow!("missing api key"))?;
-    let api_secret = ctx
-        .runtime
-        .api_secret
-        .clone()
-        .ok_or_else(|| anyhow::anyhow!("missing api secret"))?;
+    let credentials = ensure_auth(&ctx.runtime)?;
     let ws_url = ctx.runtime.ws_url().to_string();
 
-    let headers = auth::get_auth_headers(
-        &api_key,
-        &auth::parse_private_key(&api_secret),
+    // Best effort: a failed probe leaves the local clock in charge and the
+    // handshake reports any real connectivity problem.
+    let _ = KalshiClient::new(ctx.runtime.clone())?.sync_clock().await;
+    let headers = credentials.auth_headers(
+        ctx.runtime.clock.now_ms(),
         "GET",
-        "/trade-api/ws/v2",
+        &ctx.runtime.ws_sign_path(),
     )?;
 
     let mut req_builder = Request::builder().uri(&ws_url);
@@ -78,7 +71,7 @@ pub async fn run(ctx: &AppContext, cmd: WatchCmd) -> anyhow::Result<()> {
 
     writer
         .send(tokio_tungstenite::tungstenite::Message::Text(
-            serde_json::to_string(&subscribe_msg)?.into(),
+            serde_json::to_string(&subscribe_msg)?,
         ))
         .await?;
 
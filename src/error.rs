@@ -0,0 +1,261 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::output::OutputMode;
+
+/// Process exit codes. Documented in the README; keep them stable.
+pub const EXIT_GENERIC: i32 = 1;
+pub const EXIT_CONFIG: i32 = 3;
+pub const EXIT_AUTH: i32 = 4;
+pub const EXIT_NOT_FOUND: i32 = 5;
+pub const EXIT_VALIDATION: i32 = 6;
+pub const EXIT_RATE_LIMITED: i32 = 7;
+pub const EXIT_EXCHANGE_CLOSED: i32 = 8;
+pub const EXIT_NETWORK: i32 = 9;
+pub const EXIT_API: i32 = 10;
+
+/// Error body returned by Kalshi, e.g.
+/// `{"error": {"code": "insufficient_balance", "message": "...", "details": "..."}}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiError {
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KalshiError {
+    #[error("authentication failed: {0}")]
+    Auth(ApiError),
+    #[error("not found: {0}")]
+    NotFound(ApiError),
+    #[error("rate limited: {0}")]
+    RateLimited(ApiError),
+    #[error("request rejected: {0}")]
+    Validation(ApiError),
+    #[error("exchange closed: {0}")]
+    ExchangeClosed(ApiError),
+    #[error("kalshi api error: {0}")]
+    Api(ApiError),
+    #[error("network error")]
+    Network(#[from] reqwest::Error),
+    #[error("{0}")]
+    Config(String),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.status)?;
+        if let Some(code) = &self.code {
+            write!(f, " {code}")?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(details) = self.details.as_ref().filter(|d| !d.is_null()) {
+            match details {
+                Value::String(s) if !s.is_empty() => write!(f, " ({s})")?,
+                Value::String(_) => {}
+                other => write!(f, " ({other})")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ApiError {
+    /// Parses Kalshi's JSON error body, falling back to the raw text.
+    pub fn parse(status: StatusCode, text: &str) -> Self {
+        let parsed = serde_json::from_str::<Value>(text).ok();
+        let body = parsed
+            .as_ref()
+            .map(|v| v.get("error").unwrap_or(v))
+            .filter(|v| v.is_object());
+
+        let field = |key: &str| {
+            body.and_then(|b| b.get(key))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .filter(|s| !s.is_empty())
+        };
+
+        let message = field("message")
+            .or_else(|| {
+                (!text.trim().is_empty() && body.is_none()).then(|| text.trim().to_string())
+            })
+            .or_else(|| status.canonical_reason().map(str::to_string))
+            .unwrap_or_else(|| "unknown error".to_string());
+
+        Self {
+            status: status.as_u16(),
+            code: field("code"),
+            message,
+            details: body.and_then(|b| b.get("details")).cloned(),
+        }
+    }
+}
+
+/// Error codes Kalshi returns when trading is halted, as opposed to a plain
+/// validation failure that happens to mention a closed or inactive resource.
+const EXCHANGE_CLOSED_CODES: &[&str] = &[
+    "exchange_closed",
+    "exchange_paused",
+    "exchange_inactive",
+    "trading_is_paused",
+    "market_closed",
+    "market_paused",
+];
+
+impl KalshiError {
+    pub fn config(message: impl fmt::Display) -> Self {
+        Self::Config(message.to_string())
+    }
+
+    /// Classifies a non-success HTTP response.
+    pub fn from_response(status: StatusCode, text: &str) -> Self {
+        let api = ApiError::parse(status, text);
+        let code = api.code.as_deref().unwrap_or_default().to_ascii_lowercase();
+
+        if EXCHANGE_CLOSED_CODES.contains(&code.as_str()) {
+            return Self::ExchangeClosed(api);
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth(api),
+            StatusCode::NOT_FOUND => Self::NotFound(api),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(api),
+            StatusCode::BAD_REQUEST | StatusCode::CONFLICT | StatusCode::UNPROCESSABLE_ENTITY => {
+                Self::Validation(api)
+            }
+            _ => Self::Api(api),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "auth",
+            Self::NotFound(_) => "not_found",
+            Self::RateLimited(_) => "rate_limited",
+            Self::Validation(_) => "validation",
+            Self::ExchangeClosed(_) => "exchange_closed",
+            Self::Api(_) => "api",
+            Self::Network(_) => "network",
+            Self::Config(_) => "config",
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Auth(_) => EXIT_AUTH,
+            Self::NotFound(_) => EXIT_NOT_FOUND,
+            Self::RateLimited(_) => EXIT_RATE_LIMITED,
+            Self::Validation(_) => EXIT_VALIDATION,
+            Self::ExchangeClosed(_) => EXIT_EXCHANGE_CLOSED,
+            Self::Api(_) => EXIT_API,
+            Self::Network(_) => EXIT_NETWORK,
+            Self::Config(_) => EXIT_CONFIG,
+        }
+    }
+
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Auth(api)
+            | Self::NotFound(api)
+            | Self::RateLimited(api)
+            | Self::Validation(api)
+            | Self::ExchangeClosed(api)
+            | Self::Api(api) => Some(api),
+            Self::Network(_) | Self::Config(_) => None,
+        }
+    }
+}
+
+/// Prints `err` to stderr (as a JSON object in JSON mode) and returns the
+/// process exit code for it.
+pub fn report(err: &anyhow::Error, mode: OutputMode) -> i32 {
+    let kalshi = err.downcast_ref::<KalshiError>();
+    let exit_code = kalshi.map_or(EXIT_GENERIC, KalshiError::exit_code);
+
+    match mode {
+        OutputMode::Json => {
+            let mut body = json!({
+                "kind": kalshi.map_or("error", KalshiError::kind),
+                "message": format!("{err:#}"),
+                "exit_code": exit_code,
+            });
+            if let Some(api) = kalshi.and_then(KalshiError::api_error) {
+                body["status"] = json!(api.status);
+                body["code"] = json!(api.code);
+                body["api_message"] = json!(api.message);
+                body["details"] = api.details.clone().unwrap_or(Value::Null);
+            }
+            eprintln!(
+                "{}",
+                serde_json::to_string(&json!({ "error": body }))
+                    .unwrap_or_else(|_| "{}".to_string())
+            );
+        }
+        OutputMode::Table => eprintln!("error: {err:#}"),
+    }
+
+    exit_code
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::{KalshiError, EXIT_AUTH, EXIT_EXCHANGE_CLOSED, EXIT_VALIDATION};
+
+    #[test]
+    fn parses_kalshi_error_body() {
+        let err = KalshiError::from_response(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"code":"insufficient_balance","message":"Insufficient balance","details":"need 500"}}"#,
+        );
+        assert_eq!(err.kind(), "validation");
+        assert_eq!(err.exit_code(), EXIT_VALIDATION);
+        let api = err.api_error().unwrap();
+        assert_eq!(api.code.as_deref(), Some("insufficient_balance"));
+        assert_eq!(
+            api.to_string(),
+            "400 insufficient_balance: Insufficient balance (need 500)"
+        );
+    }
+
+    #[test]
+    fn classifies_by_status_and_code() {
+        let auth = KalshiError::from_response(StatusCode::UNAUTHORIZED, "");
+        assert_eq!(auth.exit_code(), EXIT_AUTH);
+        assert_eq!(auth.api_error().unwrap().message, "Unauthorized");
+
+        let closed = KalshiError::from_response(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"code":"market_closed","message":"Market is closed"}}"#,
+        );
+        assert_eq!(closed.exit_code(), EXIT_EXCHANGE_CLOSED);
+
+        let paused = KalshiError::from_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            r#"{"error":{"code":"exchange_paused","message":"Trading is paused"}}"#,
+        );
+        assert_eq!(paused.exit_code(), EXIT_EXCHANGE_CLOSED);
+
+        let unrelated = KalshiError::from_response(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"code":"order_already_closed","message":"Order is closed"}}"#,
+        );
+        assert_eq!(unrelated.exit_code(), EXIT_VALIDATION);
+    }
+
+    #[test]
+    fn keeps_plain_text_bodies() {
+        let err = KalshiError::from_response(StatusCode::BAD_GATEWAY, "upstream timeout");
+        assert_eq!(err.kind(), "api");
+        assert_eq!(err.api_error().unwrap().message, "upstream timeout");
+    }
+}
@@ -0,0 +1,626 @@
+use std::cmp::Ordering;
+
+use serde::Serialize;
+use serde_json::Value;
+
+static NULL: Value = Value::Null;
+
+/// A `--filter` expression over the JSON form of a row: comparisons
+/// (`== != < <= > >=`, `~` for case-insensitive substring) of fields and
+/// literals, combined with `&&`, `||`, `!` and parentheses. Missing fields
+/// are `null`, which only equals `null`.
+#[derive(Debug, Clone)]
+pub struct Filter {
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, CmpOp, Operand),
+    /// A bare operand: true unless null, false, 0 or "".
+    Truthy(Operand),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    /// Dotted path into nested objects, e.g. `event.title`.
+    Field(String),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Op(CmpOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+    Minus,
+}
+
+#[derive(Debug)]
+struct Spanned {
+    pos: usize,
+    text: String,
+    token: Token,
+}
+
+impl Filter {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            next: 0,
+        };
+        if parser.tokens.is_empty() {
+            return Err("empty filter".to_string());
+        }
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.next) {
+            Some(extra) => Err(format!(
+                "unexpected `{}` at position {}",
+                extra.text, extra.pos
+            )),
+            None => Ok(Self { expr }),
+        }
+    }
+
+    pub fn matches(&self, row: &Value) -> bool {
+        self.expr.eval(row)
+    }
+
+    fn fields(&self) -> Vec<&str> {
+        let mut fields = Vec::new();
+        self.expr.collect_fields(&mut fields);
+        fields
+    }
+}
+
+/// `--sort` keys: comma-separated field paths, each ascending unless
+/// prefixed with `-`. Rows missing a key sort last in either direction.
+#[derive(Debug, Clone)]
+pub struct SortKeys {
+    keys: Vec<(String, bool)>,
+}
+
+impl SortKeys {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let keys = input
+            .split(',')
+            .map(|key| {
+                let key = key.trim();
+                let (field, descending) = match key.strip_prefix('-') {
+                    Some(field) => (field, true),
+                    None => (key.strip_prefix('+').unwrap_or(key), false),
+                };
+                if is_field_path(field) {
+                    Ok((field.to_string(), descending))
+                } else {
+                    Err(format!("invalid sort key `{key}`"))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { keys })
+    }
+
+    pub fn compare(&self, a: &Value, b: &Value) -> Ordering {
+        self.keys
+            .iter()
+            .map(
+                |(field, descending)| match (lookup(a, field), lookup(b, field)) {
+                    (Value::Null, Value::Null) => Ordering::Equal,
+                    (Value::Null, _) => Ordering::Greater,
+                    (_, Value::Null) => Ordering::Less,
+                    (a, b) => {
+                        let ordering = sort_order(a, b);
+                        if *descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    }
+                },
+            )
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Drops rows not matching `filter`, then stable-sorts by `sort`, so the
+/// existing order breaks ties. Warns about fields no row has, which are
+/// usually typos.
+pub fn apply_rows<T: Serialize>(
+    rows: &mut Vec<T>,
+    filter: Option<&Filter>,
+    sort: Option<&SortKeys>,
+) -> anyhow::Result<()> {
+    if filter.is_none() && sort.is_none() {
+        return Ok(());
+    }
+    let values = rows
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fields = filter
+        .map(Filter::fields)
+        .unwrap_or_default()
+        .into_iter()
+        .chain(
+            sort.iter()
+                .flat_map(|s| s.keys.iter().map(|(f, _)| f.as_str())),
+        );
+    for field in fields {
+        if !values.is_empty() && values.iter().all(|v| lookup(v, field).is_null()) {
+            eprintln!("warning: no row has a `{field}` field");
+        }
+    }
+
+    let mut kept = values
+        .into_iter()
+        .zip(rows.drain(..))
+        .filter(|(value, _)| filter.is_none_or(|f| f.matches(value)))
+        .collect::<Vec<_>>();
+    if let Some(sort) = sort {
+        kept.sort_by(|a, b| sort.compare(&a.0, &b.0));
+    }
+    rows.extend(kept.into_iter().map(|(_, row)| row));
+    Ok(())
+}
+
+impl Expr {
+    fn eval(&self, row: &Value) -> bool {
+        match self {
+            Self::And(a, b) => a.eval(row) && b.eval(row),
+            Self::Or(a, b) => a.eval(row) || b.eval(row),
+            Self::Not(e) => !e.eval(row),
+            Self::Compare(lhs, op, rhs) => op.apply(lhs.value(row), rhs.value(row)),
+            Self::Truthy(operand) => match operand.value(row) {
+                Value::Null => false,
+                Value::Bool(b) => *b,
+                Value::Number(n) => n.as_f64() != Some(0.0),
+                Value::String(s) => !s.is_empty(),
+                _ => true,
+            },
+        }
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Self::And(a, b) | Self::Or(a, b) => {
+                a.collect_fields(fields);
+                b.collect_fields(fields);
+            }
+            Self::Not(e) => e.collect_fields(fields),
+            Self::Compare(lhs, _, rhs) => {
+                fields.extend(lhs.field());
+                fields.extend(rhs.field());
+            }
+            Self::Truthy(operand) => fields.extend(operand.field()),
+        }
+    }
+}
+
+impl Operand {
+    fn value<'a>(&'a self, row: &'a Value) -> &'a Value {
+        match self {
+            Self::Field(path) => lookup(row, path),
+            Self::Literal(value) => value,
+        }
+    }
+
+    fn field(&self) -> Option<&str> {
+        match self {
+            Self::Field(path) => Some(path),
+            Self::Literal(_) => None,
+        }
+    }
+}
+
+impl CmpOp {
+    fn apply(self, a: &Value, b: &Value) -> bool {
+        match self {
+            Self::Eq => loose_eq(a, b),
+            Self::Ne => !loose_eq(a, b),
+            Self::Lt => compare(a, b) == Some(Ordering::Less),
+            Self::Le => matches!(compare(a, b), Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => compare(a, b) == Some(Ordering::Greater),
+            Self::Ge => matches!(compare(a, b), Some(Ordering::Greater | Ordering::Equal)),
+            Self::Contains => match (scalar_text(a), scalar_text(b)) {
+                (Some(haystack), Some(needle)) => {
+                    haystack.to_lowercase().contains(&needle.to_lowercase())
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+fn lookup<'a>(row: &'a Value, path: &str) -> &'a Value {
+    path.split('.')
+        .try_fold(row, |value, key| value.get(key))
+        .unwrap_or(&NULL)
+}
+
+fn loose_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Null, Value::Null) => true,
+        (Value::Null, _) | (_, Value::Null) => false,
+        _ => compare(a, b) == Some(Ordering::Equal),
+    }
+}
+
+/// Numbers (and numeric strings such as the API's `*_dollars` fields)
+/// compare numerically, other strings lexically, which orders RFC 3339
+/// timestamps by time.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Bool(x), Value::Bool(y)) => Some(x.cmp(y)),
+        _ => match (as_number(a), as_number(b)) {
+            (Some(x), Some(y)) => x.partial_cmp(&y),
+            _ => match (a, b) {
+                (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+                _ => None,
+            },
+        },
+    }
+}
+
+/// Total order for `--sort`: booleans, then numbers (including numeric
+/// strings), then other strings lexically, then arrays and objects.
+/// Comparing across kinds by rank keeps the order transitive when a column
+/// mixes types, which `sort_by` requires.
+fn sort_order(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> (u8, Option<f64>) {
+        match value {
+            Value::Null => (0, None),
+            Value::Bool(_) => (1, None),
+            _ => match as_number(value) {
+                Some(n) => (2, Some(n)),
+                None if value.is_string() => (3, None),
+                None => (4, None),
+            },
+        }
+    }
+    let ((rank_a, num_a), (rank_b, num_b)) = (rank(a), rank(b));
+    rank_a
+        .cmp(&rank_b)
+        .then_with(|| match (a, b, num_a, num_b) {
+            (_, _, Some(x), Some(y)) => x.total_cmp(&y),
+            (Value::Bool(x), Value::Bool(y), ..) => x.cmp(y),
+            (Value::String(x), Value::String(y), ..) => x.cmp(y),
+            _ => Ordering::Equal,
+        })
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn is_field_path(text: &str) -> bool {
+    !text.is_empty()
+        && text.split('.').all(|part| {
+            part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+fn tokenize(input: &str) -> Result<Vec<Spanned>, String> {
+    let chars = input.char_indices().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        let (token, len) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('=', Some('=')) => (Token::Op(CmpOp::Eq), 2),
+            ('!', Some('=')) => (Token::Op(CmpOp::Ne), 2),
+            ('<', Some('=')) => (Token::Op(CmpOp::Le), 2),
+            ('>', Some('=')) => (Token::Op(CmpOp::Ge), 2),
+            ('=', _) => (Token::Op(CmpOp::Eq), 1),
+            ('<', _) => (Token::Op(CmpOp::Lt), 1),
+            ('>', _) => (Token::Op(CmpOp::Gt), 1),
+            ('~', _) => (Token::Op(CmpOp::Contains), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('-', _) => (Token::Minus, 1),
+            ('\'' | '"', _) => {
+                let len = chars[i + 1..]
+                    .iter()
+                    .position(|(_, ch)| *ch == c)
+                    .ok_or_else(|| format!("unterminated string at position {pos}"))?;
+                let text = chars[i + 1..i + 1 + len].iter().map(|(_, ch)| ch).collect();
+                (Token::Str(text), len + 2)
+            }
+            (c, _) if c.is_ascii_digit() || c == '.' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|(_, ch)| ch.is_ascii_digit() || *ch == '.')
+                    .count();
+                let text = chars[i..i + len]
+                    .iter()
+                    .map(|(_, ch)| ch)
+                    .collect::<String>();
+                let number = text
+                    .parse()
+                    .map_err(|_| format!("invalid number `{text}` at position {pos}"))?;
+                (Token::Number(number), len)
+            }
+            (c, _) if c.is_ascii_alphabetic() || c == '_' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|(_, ch)| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.'))
+                    .count();
+                let text = chars[i..i + len]
+                    .iter()
+                    .map(|(_, ch)| ch)
+                    .collect::<String>();
+                if !is_field_path(&text) {
+                    return Err(format!("invalid field `{text}` at position {pos}"));
+                }
+                (Token::Ident(text), len)
+            }
+            _ => return Err(format!("unexpected `{c}` at position {pos}")),
+        };
+
+        let end = chars.get(i + len).map_or(input.len(), |(end, _)| *end);
+        tokens.push(Spanned {
+            pos,
+            text: input[pos..end].to_string(),
+            token,
+        });
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent, loosest first: `||`, `&&`, then `!`, parentheses and
+/// comparisons.
+struct Parser {
+    tokens: Vec<Spanned>,
+    next: usize,
+}
+
+impl Parser {
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self
+            .tokens
+            .get(self.next)
+            .is_some_and(|t| &t.token == token);
+        if found {
+            self.next += 1;
+        }
+        found
+    }
+
+    fn advance(&mut self) -> Option<&Spanned> {
+        let token = self.tokens.get(self.next);
+        self.next += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.and()?;
+        while self.eat(&Token::Or) {
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        while self.eat(&Token::And) {
+            lhs = Expr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::LParen) {
+            let expr = self.or()?;
+            if !self.eat(&Token::RParen) {
+                return Err(self.expected("`)`"));
+            }
+            return Ok(expr);
+        }
+
+        let lhs = self.operand()?;
+        match self.tokens.get(self.next).map(|t| &t.token) {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.next += 1;
+                Ok(Expr::Compare(lhs, op, self.operand()?))
+            }
+            _ => Ok(Expr::Truthy(lhs)),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        let negative = self.eat(&Token::Minus);
+        let operand = match self.advance().map(|t| t.token.clone()) {
+            Some(Token::Number(n)) => {
+                let n = if negative { -n } else { n };
+                Operand::Literal(serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number))
+            }
+            _ if negative => {
+                self.next -= 1;
+                return Err(self.expected("a number"));
+            }
+            Some(Token::Str(text)) => Operand::Literal(Value::String(text)),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Operand::Literal(Value::Bool(true)),
+                "false" => Operand::Literal(Value::Bool(false)),
+                "null" => Operand::Literal(Value::Null),
+                _ => Operand::Field(name),
+            },
+            _ => {
+                self.next -= 1;
+                return Err(self.expected("a field or value"));
+            }
+        };
+        Ok(operand)
+    }
+
+    fn expected(&self, what: &str) -> String {
+        match self.tokens.get(self.next) {
+            Some(found) => format!(
+                "expected {what} at position {}, found `{}`",
+                found.pos, found.text
+            ),
+            None => format!("expected {what} at end of filter"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{apply_rows, Filter, SortKeys};
+
+    #[test]
+    fn filters_and_sorts_rows() {
+        let filter = Filter::parse(
+            "yes_ask < 20 && (volume_24h > 1000 || title ~ 'FED') && status != 'closed'",
+        )
+        .unwrap();
+        assert!(filter.matches(&json!({"yes_ask": 15, "volume_24h": 5000, "status": "active"})));
+        assert!(filter.matches(&json!({"yes_ask": 15, "title": "Fed cut?"})));
+        assert!(!filter.matches(&json!({"yes_ask": 25, "volume_24h": 5000})));
+        assert!(!filter.matches(&json!({"volume_24h": 5000})));
+        assert!(Filter::parse("yes_bid_dollars >= 0.5 && !result")
+            .unwrap()
+            .matches(&json!({"yes_bid_dollars": "0.5600"})));
+        assert!(Filter::parse("price > -1")
+            .unwrap()
+            .matches(&json!({"price": 0})));
+
+        assert_eq!(
+            Filter::parse("yes_ask < ").unwrap_err(),
+            "expected a field or value at end of filter"
+        );
+        assert_eq!(
+            Filter::parse("(a > 1 b").unwrap_err(),
+            "expected `)` at position 7, found `b`"
+        );
+        assert!(SortKeys::parse("-open_interest,,close_time").is_err());
+
+        let mut rows = vec![
+            json!({"t": "a", "oi": 5, "close_time": "2026-11-01T00:00:00Z"}),
+            json!({"t": "b", "oi": 9}),
+            json!({"t": "c", "oi": 5, "close_time": "2026-10-20T00:00:00Z"}),
+            json!({"t": "d", "close_time": "2026-10-01T00:00:00Z"}),
+        ];
+        let sort = SortKeys::parse("-oi,close_time").unwrap();
+        apply_rows(&mut rows, None, Some(&sort)).unwrap();
+        let order = rows
+            .iter()
+            .map(|r| r["t"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(order, ["b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn matches_substrings_nulls_and_dotted_paths() {
+        let row = json!({"title": "Fed cuts rates?", "result": null, "price": {"yes": "0.42"}});
+        let matches = |expr: &str| Filter::parse(expr).unwrap().matches(&row);
+        assert!(matches("title ~ 'FED CUT'"));
+        assert!(!matches("title ~ 'hike'"));
+        assert!(matches("price.yes ~ '42'"));
+        assert!(matches("result == null"));
+        assert!(matches("missing == null"));
+        assert!(!matches("title == null"));
+        assert!(matches("title != null"));
+        assert!(matches("price.yes > 0.4 && price.yes <= 0.42"));
+        assert!(!matches("price.no < 1"));
+    }
+
+    #[test]
+    fn sorts_mixed_types_in_a_total_order() {
+        let mut rows = [
+            json!("b"),
+            json!(10),
+            json!(true),
+            json!("9"),
+            json!(null),
+            json!("10a"),
+            json!(false),
+            json!(2.5),
+            json!({"k": 1}),
+            json!("a"),
+        ]
+        .into_iter()
+        .map(|v| json!({"v": {"x": v}}))
+        .collect::<Vec<_>>();
+        let sort = SortKeys::parse("v.x").unwrap();
+        for a in &rows {
+            for b in &rows {
+                assert_eq!(sort.compare(a, b), sort.compare(b, a).reverse());
+            }
+        }
+        apply_rows(&mut rows, None, Some(&sort)).unwrap();
+        let order = rows.iter().map(|r| &r["v"]["x"]).collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            [
+                &json!(false),
+                &json!(true),
+                &json!(2.5),
+                &json!("9"),
+                &json!(10),
+                &json!("10a"),
+                &json!("a"),
+                &json!("b"),
+                &json!({"k": 1}),
+                &json!(null),
+            ]
+        );
+
+        let sort = SortKeys::parse("-v.x").unwrap();
+        apply_rows(&mut rows, None, Some(&sort)).unwrap();
+        assert_eq!(rows[0]["v"]["x"], json!({"k": 1}));
+        assert_eq!(rows[9]["v"]["x"], json!(null), "nulls stay last");
+    }
+}
@@ -0,0 +1,619 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{KalshiClient, PageOpts},
+    clock::now_secs,
+    config::{env_name, index_path, RuntimeConfig},
+    models::{Event, Market, Series},
+    query::QueryParams,
+};
+
+/// Bumped when the file layout changes; older files are treated as missing.
+const INDEX_VERSION: u32 = 1;
+const SYNC_PAGE_SIZE: usize = 1000;
+/// Incremental syncs re-fetch markets updated this long before the previous
+/// sync, covering clock differences and updates in flight during it.
+const SYNC_OVERLAP_SECS: i64 = 300;
+/// Incremental syncs only see markets that are still open; a full sync this
+/// often drops markets that closed or settled early.
+const FULL_SYNC_EVERY_SECS: i64 = 86_400;
+/// Incremental syncs look up at most this many new events (and series) one
+/// by one; beyond that listing them all is cheaper.
+const MAX_LOOKUPS: usize = 50;
+/// A sync lock older than this is assumed to be left over from a crash.
+const STALE_LOCK: Duration = Duration::from_secs(600);
+
+/// Open markets, events and series of one environment, cached on disk for
+/// `markets search`, shell completion and ticker checks. Markets and events
+/// are trimmed to the fields those need (no rules or extra API fields).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketIndex {
+    pub version: u32,
+    /// REST base URL the index was built from; a different URL (a mock, a
+    /// proxy) gets its own data, so a mismatch reads as no index.
+    pub base_url: String,
+    /// Unix seconds of the last successful sync.
+    pub synced_at: i64,
+    pub full_synced_at: i64,
+    pub markets: Vec<Market>,
+    pub events: Vec<Event>,
+    pub series: Vec<Series>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncReport {
+    pub full: bool,
+    /// Markets returned by the API in this sync.
+    pub fetched: usize,
+    /// Markets in the previous index that closed or were not returned.
+    pub removed: usize,
+    pub markets: usize,
+    pub events: usize,
+    pub series: usize,
+    pub elapsed_ms: u128,
+    pub path: PathBuf,
+}
+
+impl MarketIndex {
+    /// The index for the runtime's environment and base URL, if one exists.
+    pub fn load(runtime: &RuntimeConfig) -> anyhow::Result<Option<Self>> {
+        let path = index_path(env_name(runtime.environment))?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let index: Self = serde_json::from_slice(&fs::read(&path)?)
+            .map_err(|err| anyhow::anyhow!("invalid market index {}: {err}", path.display()))?;
+        Ok(
+            (index.version == INDEX_VERSION && index.base_url == runtime.rest_base_url())
+                .then_some(index),
+        )
+    }
+
+    pub fn age_secs(&self) -> i64 {
+        now_secs() - self.synced_at
+    }
+
+    pub fn market(&self, ticker: &str) -> Option<&Market> {
+        self.markets
+            .iter()
+            .find(|m| m.ticker.eq_ignore_ascii_case(ticker))
+    }
+
+    pub fn events_by_ticker(&self) -> HashMap<&str, &Event> {
+        self.events
+            .iter()
+            .map(|e| (e.event_ticker.as_str(), e))
+            .collect()
+    }
+
+    /// Number of indexed (open) markets per event ticker.
+    pub fn event_market_counts(&self) -> HashMap<&str, i64> {
+        let mut counts = HashMap::new();
+        for event_ticker in self
+            .markets
+            .iter()
+            .filter_map(|m| m.event_ticker.as_deref())
+        {
+            *counts.entry(event_ticker).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Market, event and series tickers, sorted and deduplicated.
+    pub fn tickers(&self) -> Vec<String> {
+        let mut tickers = self
+            .markets
+            .iter()
+            .map(|m| m.ticker.clone())
+            .chain(self.events.iter().map(|e| e.event_ticker.clone()))
+            .chain(self.series.iter().map(|s| s.ticker.clone()))
+            .collect::<Vec<_>>();
+        tickers.sort();
+        tickers.dedup();
+        tickers
+    }
+
+    /// Indexed market tickers sharing the longest prefix with `ticker`, which
+    /// puts markets of the same event and series first.
+    pub fn suggest(&self, ticker: &str, limit: usize) -> Vec<&str> {
+        let wanted = ticker.to_ascii_uppercase();
+        let mut scored = self
+            .markets
+            .iter()
+            .map(|m| {
+                let shared = m
+                    .ticker
+                    .chars()
+                    .zip(wanted.chars())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                (shared, m.ticker.as_str())
+            })
+            .filter(|(shared, _)| *shared >= 3)
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        scored.into_iter().take(limit).map(|(_, t)| t).collect()
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Readers never see a half-written file.
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Why the last sync failed, kept next to the index until a sync succeeds so
+/// failures of background syncs (which have no terminal) can be reported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncError {
+    /// Unix seconds.
+    pub at: i64,
+    pub base_url: String,
+    pub message: String,
+}
+
+impl SyncError {
+    fn path(index: &Path) -> PathBuf {
+        index.with_extension("error.json")
+    }
+
+    /// The recorded failure for the runtime's environment and base URL.
+    pub fn load(runtime: &RuntimeConfig) -> Option<Self> {
+        let path = Self::path(&index_path(env_name(runtime.environment)).ok()?);
+        let error: Self = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+        (error.base_url == runtime.rest_base_url()).then_some(error)
+    }
+
+    pub fn age_secs(&self) -> i64 {
+        now_secs() - self.at
+    }
+}
+
+/// Refreshes the index: open markets updated since the last sync (all of them
+/// when `full`, on first sync or once a day), plus the events and series of
+/// those markets. Fails if another sync holds the lock; any other failure is
+/// recorded as the [`SyncError`].
+pub async fn sync(
+    client: &KalshiClient,
+    runtime: &RuntimeConfig,
+    full: bool,
+) -> anyhow::Result<SyncReport> {
+    let path = index_path(env_name(runtime.environment))?;
+    let _lock = SyncLock::acquire(&path)?;
+
+    let result = sync_locked(client, runtime, &path, full).await;
+    let error_path = SyncError::path(&path);
+    match &result {
+        Ok(_) => {
+            let _ = fs::remove_file(&error_path);
+        }
+        Err(err) => {
+            let error = SyncError {
+                at: now_secs(),
+                base_url: runtime.rest_base_url().to_string(),
+                message: format!("{err:#}"),
+            };
+            let _ = fs::write(&error_path, serde_json::to_vec(&error)?);
+        }
+    }
+    result
+}
+
+async fn sync_locked(
+    client: &KalshiClient,
+    runtime: &RuntimeConfig,
+    path: &Path,
+    full: bool,
+) -> anyhow::Result<SyncReport> {
+    let started = Instant::now();
+    let previous = MarketIndex::load(runtime).unwrap_or(None);
+    let now = now_secs();
+    let full = full
+        || previous
+            .as_ref()
+            .is_none_or(|p| now - p.full_synced_at >= FULL_SYNC_EVERY_SECS);
+    // What an incremental sync builds on; a full sync starts from scratch.
+    let base = previous.as_ref().filter(|_| !full);
+
+    let since = base.map(|p| p.synced_at - SYNC_OVERLAP_SECS);
+    let q = QueryParams::new()
+        .insert("status", "open")
+        .optional("min_updated_ts", since)
+        .build_always();
+    let opts = PageOpts {
+        page_size: Some(SYNC_PAGE_SIZE),
+        max_items: None,
+    };
+    let fetched: Vec<Market> = client.collect_pages(q, opts).await?;
+    let fetched_count = fetched.len();
+    let merged = merge_markets(previous.as_ref(), full, fetched, now);
+
+    // Incremental syncs only look up events and series not indexed yet.
+    let known_events = base.map(|p| p.events.clone()).unwrap_or_default();
+    let missing = missing_keys(
+        merged
+            .markets
+            .iter()
+            .filter_map(|m| m.event_ticker.as_deref()),
+        known_events.iter().map(|e| e.event_ticker.as_str()),
+    );
+    let fetched_events = if base.is_none() || missing.len() > MAX_LOOKUPS {
+        let q = QueryParams::new().insert("status", "open").build_always();
+        client.collect_pages(q, PageOpts::default()).await?
+    } else {
+        let mut events = Vec::with_capacity(missing.len());
+        for ticker in &missing {
+            events.push(client.get_event(ticker, false).await?.into_inner().event);
+        }
+        events
+    };
+    let events = merge_keyed(
+        known_events,
+        fetched_events.into_iter().map(slim_event).collect(),
+        |e| &e.event_ticker,
+        &merged
+            .markets
+            .iter()
+            .filter_map(|m| m.event_ticker.as_deref())
+            .collect(),
+    );
+
+    let known_series = base.map(|p| p.series.clone()).unwrap_or_default();
+    let missing = missing_keys(
+        events.iter().filter_map(|e| e.series_ticker.as_deref()),
+        known_series.iter().map(|s| s.ticker.as_str()),
+    );
+    let fetched_series = if base.is_none() || missing.len() > MAX_LOOKUPS {
+        client.list_series(None, None).await?
+    } else {
+        let mut series = Vec::with_capacity(missing.len());
+        for ticker in &missing {
+            series.push(client.get_series(ticker).await?);
+        }
+        series
+    };
+    let series = merge_keyed(
+        known_series,
+        fetched_series,
+        |s| &s.ticker,
+        &events
+            .iter()
+            .filter_map(|e| e.series_ticker.as_deref())
+            .collect(),
+    );
+
+    let index = MarketIndex {
+        version: INDEX_VERSION,
+        base_url: runtime.rest_base_url().to_string(),
+        synced_at: now,
+        full_synced_at: merged.full_synced_at,
+        markets: merged.markets,
+        events,
+        series,
+    };
+    index.save(path)?;
+
+    Ok(SyncReport {
+        full,
+        fetched: fetched_count,
+        removed: merged.removed,
+        markets: index.markets.len(),
+        events: index.events.len(),
+        series: index.series.len(),
+        elapsed_ms: started.elapsed().as_millis(),
+        path: path.to_path_buf(),
+    })
+}
+
+/// Markets of the index after folding in one sync's `fetched` markets.
+#[derive(Debug)]
+struct MergedMarkets {
+    /// Open markets, sorted by ticker.
+    markets: Vec<Market>,
+    /// Markets of `previous` no longer in `markets`.
+    removed: usize,
+    full_synced_at: i64,
+}
+
+/// An incremental sync updates `previous` with `fetched`; a `full` one
+/// replaces it. Either way only markets still open at `now` are kept.
+fn merge_markets(
+    previous: Option<&MarketIndex>,
+    full: bool,
+    fetched: Vec<Market>,
+    now: i64,
+) -> MergedMarkets {
+    let mut markets: BTreeMap<String, Market> = match previous.filter(|_| !full) {
+        Some(p) => p
+            .markets
+            .iter()
+            .map(|m| (m.ticker.clone(), m.clone()))
+            .collect(),
+        None => BTreeMap::new(),
+    };
+    for market in fetched {
+        markets.insert(market.ticker.clone(), slim_market(market));
+    }
+    markets.retain(|_, m| is_open(m, now));
+
+    let removed = previous.map_or(0, |p| {
+        p.markets
+            .iter()
+            .filter(|m| !markets.contains_key(&m.ticker))
+            .count()
+    });
+    let full_synced_at = match previous {
+        Some(p) if !full => p.full_synced_at,
+        _ => now,
+    };
+    MergedMarkets {
+        markets: markets.into_values().collect(),
+        removed,
+        full_synced_at,
+    }
+}
+
+/// `wanted` keys not among `known`, sorted and deduplicated.
+fn missing_keys<'a>(
+    wanted: impl Iterator<Item = &'a str>,
+    known: impl Iterator<Item = &'a str>,
+) -> Vec<String> {
+    let known = known.collect::<HashSet<_>>();
+    wanted
+        .filter(|key| !known.contains(key))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+/// `known` rows updated with `fetched` ones of the same key, keeping only
+/// rows whose key is in `keep`, sorted by key.
+fn merge_keyed<T>(
+    known: Vec<T>,
+    fetched: Vec<T>,
+    key: impl Fn(&T) -> &String,
+    keep: &HashSet<&str>,
+) -> Vec<T> {
+    let mut rows = BTreeMap::new();
+    for row in known.into_iter().chain(fetched) {
+        rows.insert(key(&row).clone(), row);
+    }
+    rows.retain(|key, _| keep.contains(key.as_str()));
+    rows.into_values().collect()
+}
+
+/// Starts `kal index sync` as a detached process for the runtime's profile,
+/// environment and base URL, unless a sync is already running. The signing
+/// key is not needed: the index only uses public endpoints.
+pub fn spawn_background_sync(runtime: &RuntimeConfig) -> anyhow::Result<bool> {
+    let path = index_path(env_name(runtime.environment))?;
+    if SyncLock::is_held(&path) {
+        return Ok(false);
+    }
+    let mut command = Command::new(std::env::current_exe()?);
+    command.arg("--no-env-file");
+    if let Some(profile) = &runtime.profile {
+        command.args(["--profile", profile]);
+    }
+    command
+        .args([
+            "--env",
+            env_name(runtime.environment),
+            "--base-url",
+            runtime.rest_base_url(),
+            "index",
+            "sync",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(true)
+}
+
+fn slim_market(mut market: Market) -> Market {
+    market.rules_primary = None;
+    market.rules_secondary = None;
+    market.extra.clear();
+    market
+}
+
+fn slim_event(mut event: Event) -> Event {
+    event.markets = None;
+    event.extra.clear();
+    event
+}
+
+fn is_open(market: &Market, now: i64) -> bool {
+    let active = market
+        .status
+        .as_deref()
+        .is_none_or(|s| s.eq_ignore_ascii_case("active") || s.eq_ignore_ascii_case("open"));
+    active && market.close_ts().is_none_or(|ts| ts > now)
+}
+
+/// Lock file next to the index, removed on drop.
+struct SyncLock {
+    path: PathBuf,
+}
+
+impl SyncLock {
+    fn lock_path(index: &Path) -> PathBuf {
+        index.with_extension("lock")
+    }
+
+    fn is_held(index: &Path) -> bool {
+        let path = Self::lock_path(index);
+        fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age < STALE_LOCK)
+    }
+
+    fn acquire(index: &Path) -> anyhow::Result<Self> {
+        let path = Self::lock_path(index);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if path.exists() && !Self::is_held(index) {
+            let _ = fs::remove_file(&path);
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|err| match err.kind() {
+                std::io::ErrorKind::AlreadyExists => anyhow::anyhow!(
+                    "another index sync is running (lock file {})",
+                    path.display()
+                ),
+                _ => err.into(),
+            })?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for SyncLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::collections::HashSet;
+
+    use super::{is_open, merge_keyed, merge_markets, missing_keys, MarketIndex};
+    use crate::models::Market;
+
+    fn market(ticker: &str, status: &str, close: &str) -> Market {
+        serde_json::from_value(json!({
+            "ticker": ticker,
+            "event_ticker": ticker.rsplit_once('-').unwrap().0,
+            "status": status,
+            "close_time": close,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn keeps_open_markets_and_suggests_neighbours() {
+        let now = 1_800_000_000; // 2027-01-15
+        assert!(is_open(
+            &market("KXA-1-Y", "active", "2027-02-01T00:00:00Z"),
+            now
+        ));
+        assert!(!is_open(
+            &market("KXA-1-Y", "active", "2026-02-01T00:00:00Z"),
+            now
+        ));
+        assert!(!is_open(
+            &market("KXA-1-Y", "settled", "2027-02-01T00:00:00Z"),
+            now
+        ));
+
+        let index = MarketIndex {
+            markets: vec![
+                market("KXHIGHNY-26OCT18-B45", "active", ""),
+                market("KXHIGHNY-26OCT18-B47", "active", ""),
+                market("KXHIGHCHI-26OCT18-B50", "active", ""),
+                market("KXBTC-26OCT18-T100", "active", ""),
+            ],
+            ..MarketIndex::default()
+        };
+        assert!(index.market("kxhighny-26oct18-b45").is_some());
+        assert_eq!(
+            index.suggest("KXHIGHNY-26OCT18-B46", 2),
+            ["KXHIGHNY-26OCT18-B45", "KXHIGHNY-26OCT18-B47"]
+        );
+        assert_eq!(index.event_market_counts()["KXHIGHNY-26OCT18"], 2);
+    }
+
+    #[test]
+    fn merges_incremental_and_full_syncs() {
+        let now = 1_800_000_000; // 2027-01-15
+        let open = "2027-02-01T00:00:00Z";
+        let previous = MarketIndex {
+            synced_at: now - 600,
+            full_synced_at: now - 3_600,
+            markets: vec![
+                market("KXA-1-Y", "active", open),
+                market("KXA-1-N", "active", open),
+                market("KXB-1-Y", "active", "2027-01-10T00:00:00Z"),
+            ],
+            ..MarketIndex::default()
+        };
+        let mut updated = market("KXA-1-Y", "active", open);
+        updated.volume = Some(7);
+
+        // Incremental: previous markets stay, updates win, closed ones go.
+        let merged = merge_markets(
+            Some(&previous),
+            false,
+            vec![
+                updated,
+                market("KXA-1-N", "settled", open),
+                market("KXC-1-Y", "active", open),
+            ],
+            now,
+        );
+        let tickers = merged.markets.iter().map(|m| m.ticker.as_str());
+        assert_eq!(tickers.collect::<Vec<_>>(), ["KXA-1-Y", "KXC-1-Y"]);
+        assert_eq!(merged.markets[0].volume, Some(7));
+        assert_eq!(merged.removed, 2);
+        assert_eq!(merged.full_synced_at, now - 3_600);
+
+        // Full: only what was fetched.
+        let merged = merge_markets(
+            Some(&previous),
+            true,
+            vec![market("KXC-1-Y", "active", open)],
+            now,
+        );
+        assert_eq!(merged.markets.len(), 1);
+        assert_eq!(merged.removed, 3);
+        assert_eq!(merged.full_synced_at, now);
+        assert_eq!(
+            merge_markets(None, false, Vec::new(), now).full_synced_at,
+            now
+        );
+    }
+
+    #[test]
+    fn looks_up_only_missing_keys_and_prunes_unreferenced_rows() {
+        assert_eq!(
+            missing_keys(["B", "A", "C", "A"].into_iter(), ["C"].into_iter()),
+            ["A", "B"]
+        );
+
+        let keep = HashSet::from(["A", "B"]);
+        let rows = merge_keyed(
+            vec![("A".to_string(), 1), ("Z".to_string(), 1)],
+            vec![("A".to_string(), 2), ("B".to_string(), 2)],
+            |row| &row.0,
+            &keep,
+        );
+        assert_eq!(
+            rows,
+            [("A".to_string(), 2), ("B".to_string(), 2)],
+            "fetched rows replace known ones; unreferenced ones are dropped"
+        );
+    }
+}
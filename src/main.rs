@@ -1,14 +1,32 @@
 mod auth;
 mod client;
+mod clock;
 mod commands;
 mod config;
+mod error;
+mod filter;
+mod index;
+mod models;
 mod output;
 mod query;
+mod ratelimit;
+mod search;
+mod trace;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
-use commands::{config_cmd, events, exchange, markets, order, portfolio, shell, trades, watch};
-use config::{resolve_runtime_config, Environment, RuntimeConfig};
-use output::OutputMode;
+use commands::{
+    apikeys, config_cmd, events, exchange, index_cmd, markets, order, portfolio, search_aliases,
+    series, shell, trades, watch,
+};
+use config::{
+    preferred_output, profile_preferences, requested_profile, resolve_settings, CliOverrides,
+    EnvVars, Environment, RuntimeConfig,
+};
+use error::KalshiError;
+use output::{DisplayOptions, OutputMode};
+use ratelimit::RateLimit;
+use std::{path::PathBuf, sync::Arc};
+use trace::{HttpTracer, TraceLevel};
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum OutputFormat {
@@ -18,14 +36,13 @@ enum OutputFormat {
 
 #[derive(Debug, Clone, Args)]
 struct GlobalOpts {
-    #[arg(
-        short = 'o',
-        long = "output",
-        value_enum,
-        default_value = "table",
-        global = true
-    )]
-    output: OutputFormat,
+    /// Config profile to use (env: KALSHI_PROFILE)
+    #[arg(short = 'p', long = "profile", global = true)]
+    profile: Option<String>,
+
+    /// Output format [default: table, or the profile's `output` preference]
+    #[arg(short = 'o', long = "output", value_enum, global = true)]
+    output: Option<OutputFormat>,
 
     #[arg(short = 'e', long = "env", value_enum, global = true)]
     environment: Option<Environment>,
@@ -35,6 +52,42 @@ struct GlobalOpts {
 
     #[arg(long = "api-secret", global = true)]
     api_secret: Option<String>,
+
+    /// Read KALSHI_* variables from this file instead of ./.env
+    #[arg(long = "env-file", global = true, value_name = "PATH")]
+    env_file: Option<PathBuf>,
+
+    /// Do not read ./.env
+    #[arg(long = "no-env-file", global = true, conflicts_with = "env_file")]
+    no_env_file: bool,
+
+    /// Override the REST base URL (e.g. a local mock or recording proxy)
+    #[arg(long = "base-url", global = true)]
+    base_url: Option<String>,
+
+    /// Override the WebSocket URL used by `watch`
+    #[arg(long = "ws-url", global = true)]
+    ws_url: Option<String>,
+
+    /// Client-side request budget: basic|advanced|premier|prime, off, N or READ/WRITE per second
+    #[arg(long = "rate-limit", global = true)]
+    rate_limit: Option<RateLimit>,
+
+    /// Print a debug summary (rate limiter counters) to stderr when the command finishes
+    #[arg(long, global = true)]
+    debug: bool,
+
+    /// Log each HTTP request (method, URL, status, latency, retries) to stderr
+    #[arg(short = 'v', long, global = true)]
+    verbose: bool,
+
+    /// Like --verbose, plus redacted headers and request/response bodies
+    #[arg(long = "trace-http", global = true)]
+    trace_http: bool,
+
+    /// Append a full NDJSON trace of every HTTP request to this file
+    #[arg(long = "trace-file", global = true, value_name = "PATH")]
+    trace_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Parser)]
@@ -51,12 +104,20 @@ struct Cli {
 enum Commands {
     Markets(markets::MarketsCmd),
     Events(events::EventsCmd),
+    Series(series::SeriesCmd),
     Order(order::OrderCmd),
     Portfolio(portfolio::PortfolioCmd),
     Trades(trades::TradesCmd),
     Exchange(exchange::ExchangeCmd),
     Watch(watch::WatchCmd),
+    #[command(name = "apikeys")]
+    ApiKeys(apikeys::ApiKeysCmd),
     Config(config_cmd::ConfigCmd),
+    /// Local market index for instant `markets search`
+    Index(index_cmd::IndexCmd),
+    /// Maintain the `markets search` alias dictionary
+    #[command(name = "search-aliases")]
+    SearchAliases(search_aliases::SearchAliasesCmd),
     Shell,
 }
 
@@ -64,6 +125,8 @@ enum Commands {
 pub struct AppContext {
     pub runtime: RuntimeConfig,
     pub output_mode: OutputMode,
+    /// Table formatting preferences of the selected profile.
+    pub display: DisplayOptions,
 }
 
 impl From<OutputFormat> for OutputMode {
@@ -77,44 +140,139 @@ impl From<OutputFormat> for OutputMode {
 
 #[tokio::main]
 async fn main() {
-    if let Err(err) = run().await {
-        eprintln!("error: {err}");
-        std::process::exit(1);
+    let cli = Cli::parse();
+    let output_mode = output_mode(&cli.global);
+    if let Err(err) = dispatch(cli, None).await {
+        std::process::exit(error::report(&err, output_mode));
     }
 }
 
-async fn run() -> anyhow::Result<()> {
-    let cli = Cli::parse();
-    dispatch(cli).await
+/// `--output`, else the selected profile's preference, else table.
+fn output_mode(global: &GlobalOpts) -> OutputMode {
+    global
+        .output
+        .map(OutputMode::from)
+        .or_else(|| {
+            // Best effort: a broken env file is reported by `dispatch`.
+            let env =
+                EnvVars::load(global.env_file.as_deref(), global.no_env_file).unwrap_or_default();
+            preferred_output(requested_profile(global.profile.clone(), &env).as_deref())
+        })
+        .unwrap_or_default()
 }
 
-pub(crate) async fn dispatch(cli: Cli) -> anyhow::Result<()> {
-    let output_mode = OutputMode::from(cli.global.output);
+/// Runs one command line. `session` is the runtime of an enclosing `kal shell`,
+/// whose already-loaded signing key is reused.
+pub(crate) async fn dispatch(cli: Cli, session: Option<&RuntimeConfig>) -> anyhow::Result<()> {
+    let output_mode = output_mode(&cli.global);
+    let debug = cli.global.debug;
+
+    let trace_level = if cli.global.trace_http {
+        TraceLevel::Full
+    } else if cli.global.verbose {
+        TraceLevel::Summary
+    } else {
+        TraceLevel::Off
+    };
+    let tracer = HttpTracer::new(trace_level, cli.global.trace_file.as_deref())
+        .map_err(KalshiError::config)?;
+
+    let env = EnvVars::load(cli.global.env_file.as_deref(), cli.global.no_env_file)
+        .map_err(|err| KalshiError::config(format!("{err:#}")))?;
+
+    let overrides = CliOverrides {
+        profile: requested_profile(cli.global.profile, &env),
+        environment: cli.global.environment,
+        api_key: cli.global.api_key,
+        api_secret: cli.global.api_secret,
+        base_url: cli.global.base_url,
+        ws_url: cli.global.ws_url,
+        rate_limit: cli.global.rate_limit,
+        tracer: Arc::new(tracer),
+        env,
+        // Public endpoints only; background syncs must not need to read (or
+        // prompt to decrypt) the signing key.
+        public_only: matches!(cli.command, Commands::Index(_)),
+    };
 
     if let Commands::Config(cmd) = &cli.command {
-        return config_cmd::run(cmd.clone(), output_mode).await;
+        return config_cmd::run(cmd.clone(), overrides, output_mode).await;
+    }
+    // Local files only; of the profile, just its display preferences apply.
+    if let Commands::SearchAliases(cmd) = &cli.command {
+        let display = profile_preferences(overrides.profile.as_deref())
+            .map_err(|err| KalshiError::config(format!("{err:#}")))?
+            .display_options()
+            .map_err(|err| KalshiError::config(format!("invalid preferences: {err:#}")))?;
+        return search_aliases::run(cmd.clone(), output_mode, &display).await;
     }
 
-    let runtime = resolve_runtime_config(
-        cli.global.environment,
-        cli.global.api_key,
-        cli.global.api_secret,
-    )?;
+    let settings =
+        resolve_settings(overrides).map_err(|err| KalshiError::config(format!("{err:#}")))?;
+    let mut runtime = settings
+        .into_runtime()
+        .map_err(|err| KalshiError::config(format!("{err:#}")))?;
+    if let Some(session) = session {
+        runtime.share_credentials(session);
+    }
+    let display = runtime
+        .preferences
+        .display_options()
+        .map_err(|err| KalshiError::config(format!("invalid preferences: {err:#}")))?;
 
     let ctx = AppContext {
         runtime,
         output_mode,
+        display,
     };
 
-    match cli.command {
+    let result = match cli.command {
         Commands::Markets(cmd) => markets::run(&ctx, cmd).await,
         Commands::Events(cmd) => events::run(&ctx, cmd).await,
+        Commands::Series(cmd) => series::run(&ctx, cmd).await,
         Commands::Order(cmd) => order::run(&ctx, cmd).await,
         Commands::Portfolio(cmd) => portfolio::run(&ctx, cmd).await,
         Commands::Trades(cmd) => trades::run(&ctx, cmd).await,
         Commands::Exchange(cmd) => exchange::run(&ctx, cmd).await,
         Commands::Watch(cmd) => watch::run(&ctx, cmd).await,
-        Commands::Shell => shell::run(output_mode).await,
-        Commands::Config(_) => unreachable!(),
+        Commands::ApiKeys(cmd) => apikeys::run(&ctx, cmd).await,
+        Commands::Index(cmd) => index_cmd::run(&ctx, cmd).await,
+        Commands::Shell => shell::run(&ctx.runtime, output_mode).await,
+        Commands::Config(_) | Commands::SearchAliases(_) => unreachable!(),
+    };
+
+    if debug {
+        print_debug_summary(&ctx);
+    }
+    result
+}
+
+fn print_debug_summary(ctx: &AppContext) {
+    let limit = ctx.runtime.limiter.limit();
+    let stats = ctx.runtime.limiter.stats();
+    let signing_key = ctx
+        .runtime
+        .credentials
+        .as_deref()
+        .and_then(|c| c.loaded())
+        .map(|c| format!("{}-bit RSA ({})", c.key_bits(), c.key_format()));
+    match ctx.output_mode {
+        OutputMode::Json => eprintln!(
+            "{}",
+            serde_json::json!({"debug": {
+                "rate_limit": limit.to_string(),
+                "requests": stats,
+                "signing_key": signing_key,
+            }})
+        ),
+        OutputMode::Table => eprintln!(
+            "debug: rate limit {limit} req/s (read/write); reads={} writes={} throttled={} waited={}ms retries={}; signing key {}",
+            stats.reads,
+            stats.writes,
+            stats.throttled,
+            stats.waited_ms,
+            stats.retries,
+            signing_key.as_deref().unwrap_or("none")
+        ),
     }
 }
@@ -0,0 +1,933 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// Fields the CLI does not model explicitly. Flattened into every type so
+/// `-o json` round-trips whatever the API returned.
+pub type Extra = Map<String, Value>;
+
+/// A response model together with the JSON object it was parsed from.
+/// Serializing re-emits every field the CLI did not change exactly as the
+/// API sent it (numeric strings, floats, explicit nulls); changed and added
+/// fields are emitted as they are now.
+#[derive(Debug, Clone, Default)]
+pub struct Raw<T> {
+    fields: T,
+    original: Option<Original>,
+}
+
+/// The object a [`Raw`] was parsed from, captured once so serializing never
+/// has to parse it again.
+#[derive(Debug, Clone)]
+struct Original {
+    raw: Extra,
+    /// What the fields serialized to before the CLI touched them.
+    parsed: Extra,
+}
+
+impl<T> From<T> for Raw<T> {
+    fn from(fields: T) -> Self {
+        Self {
+            fields,
+            original: None,
+        }
+    }
+}
+
+impl<T> Raw<T> {
+    pub fn into_inner(self) -> T {
+        self.fields
+    }
+}
+
+impl<T> Deref for Raw<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.fields
+    }
+}
+
+impl<T> DerefMut for Raw<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.fields
+    }
+}
+
+fn into_object(value: Value) -> Extra {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+impl<'de, T: DeserializeOwned + Serialize> Deserialize<'de> for Raw<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let fields = T::deserialize(&value).map_err(de::Error::custom)?;
+        let original = match value {
+            Value::Object(raw) => {
+                let parsed = serde_json::to_value(&fields).map_err(de::Error::custom)?;
+                Some(Original {
+                    raw,
+                    parsed: into_object(parsed),
+                })
+            }
+            _ => None,
+        };
+        Ok(Self { fields, original })
+    }
+}
+
+impl<T: Serialize> Serialize for Raw<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Some(Original { raw, parsed }) = &self.original else {
+            return self.fields.serialize(serializer);
+        };
+        let current =
+            into_object(serde_json::to_value(&self.fields).map_err(serde::ser::Error::custom)?);
+        if current == *parsed {
+            return raw.serialize(serializer);
+        }
+
+        let mut merged = Map::new();
+        for (key, raw_value) in raw {
+            match current.get(key) {
+                value if value == parsed.get(key) => {
+                    merged.insert(key.clone(), raw_value.clone());
+                }
+                Some(value) => {
+                    merged.insert(key.clone(), value.clone());
+                }
+                None => {}
+            }
+        }
+        for (key, value) in current {
+            if !raw.contains_key(&key) {
+                merged.insert(key, value);
+            }
+        }
+        merged.serialize(serializer)
+    }
+}
+
+pub type Market = Raw<MarketFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketFields {
+    #[serde(default)]
+    pub ticker: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_ticker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yes_sub_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_sub_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_time: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yes_bid: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yes_ask: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub no_bid: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub no_ask: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_price: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub volume: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub volume_24h: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub open_volume: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub open_interest: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub liquidity: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules_primary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules_secondary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor_strike: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cap_strike: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strike_type: Option<String>,
+    /// Number of markets in the parent event; filled in by the CLI, not the API.
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub event_market_count: Option<i64>,
+    /// Relevance to the `markets search` query; filled in by the CLI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_score: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl Market {
+    /// Volume used for ranking: open volume when present, else lifetime, else 24h.
+    pub fn ranking_volume(&self) -> i64 {
+        self.open_volume
+            .or(self.volume)
+            .or(self.volume_24h)
+            .unwrap_or(0)
+    }
+
+    /// Best available YES price: ask, then bid, then last trade.
+    pub fn yes_price(&self) -> Option<i64> {
+        self.yes_ask.or(self.yes_bid).or(self.last_price)
+    }
+
+    /// Close time in unix seconds, from `close_time` (RFC 3339 or seconds),
+    /// `close_ts`, or the expiration fields.
+    pub fn close_ts(&self) -> Option<i64> {
+        let parse = |v: Option<&str>| {
+            v.and_then(|s| {
+                s.parse::<i64>()
+                    .ok()
+                    .or_else(|| s.parse::<jiff::Timestamp>().ok().map(|ts| ts.as_second()))
+            })
+        };
+        parse(self.close_time.as_deref())
+            .or_else(|| self.extra.get("close_ts").and_then(value_as_i64))
+            .or_else(|| self.extra.get("expiration_ts").and_then(value_as_i64))
+            .or_else(|| parse(self.expiration_time.as_deref()))
+    }
+}
+
+pub type Event = Raw<EventFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventFields {
+    #[serde(default)]
+    pub event_ticker: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series_ticker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mutually_exclusive: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markets: Option<Vec<Market>>,
+    /// Aggregates computed by `events top`; absent on raw API rows.
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub market_count: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub total_volume: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub open_interest: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type EventDetail = Raw<EventDetailFields>;
+
+/// Response of `GET /events/{ticker}`. Markets arrive either nested in the
+/// event or alongside it depending on `with_nested_markets`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventDetailFields {
+    pub event: Event,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markets: Option<Vec<Market>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl EventDetail {
+    pub fn market_count(&self) -> Option<usize> {
+        self.event
+            .markets
+            .as_ref()
+            .or(self.markets.as_ref())
+            .map(Vec::len)
+    }
+}
+
+pub type Series = Raw<SeriesFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeriesFields {
+    #[serde(default)]
+    pub ticker: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlement_sources: Option<Vec<SettlementSource>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_multiplier: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettlementSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Order = Raw<OrderFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderFields {
+    #[serde(default)]
+    pub order_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yes_price: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub no_price: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count_fp: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub remaining_count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_count_fp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Body of `POST /portfolio/orders`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateOrderRequest {
+    pub ticker: String,
+    pub side: String,
+    pub action: String,
+    pub count: i64,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub time_in_force: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yes_price: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_price: Option<i64>,
+    /// Idempotency key; `KalshiClient::create_order` generates one when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+}
+
+/// Body of `POST /portfolio/orders/{id}/amend`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AmendOrderRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yes_price: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+}
+
+pub type AmendedOrder = Raw<AmendedOrderFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AmendedOrderFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_order: Option<Order>,
+    pub order: Order,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type CanceledOrder = Raw<CanceledOrderFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CanceledOrderFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<Order>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reduced_by: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Fill = Raw<FillFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FillFields {
+    #[serde(default)]
+    pub trade_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub count: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yes_price: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub no_price: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_taker: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Settlement = Raw<SettlementFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettlementFields {
+    #[serde(default)]
+    pub ticker: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_result: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yes_count: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub no_count: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub revenue: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub realized_pnl: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settled_time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Position = Raw<PositionFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionFields {
+    #[serde(default)]
+    pub ticker: String,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub position: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub market_exposure: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub realized_pnl: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub total_traded: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fees_paid: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_result: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_title: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Balance = Raw<BalanceFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BalanceFields {
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub balance: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub portfolio_value: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type ApiKey = Raw<ApiKeyFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiKeyFields {
+    #[serde(default)]
+    pub api_key_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Body of `POST /api_keys`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    /// RSA public key as `BEGIN PUBLIC KEY` PEM.
+    pub public_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+}
+
+pub type CreatedApiKey = Raw<CreatedApiKeyFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreatedApiKeyFields {
+    #[serde(default)]
+    pub api_key_id: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Candlestick = Raw<CandlestickFields>;
+
+/// One period of `/series/{series}/markets/{ticker}/candlesticks`. `price`
+/// covers traded prices and is empty for periods without trades.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CandlestickFields {
+    #[serde(default)]
+    pub end_period_ts: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yes_bid: Option<Ohlc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yes_ask: Option<Ohlc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<Ohlc>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub volume: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub open_interest: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Open/high/low/close in cents.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ohlc {
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub open: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub high: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub low: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub close: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type Trade = Raw<TradeFields>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TradeFields {
+    #[serde(default)]
+    pub trade_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticker: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub count: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yes_price: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient_i64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub no_price: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taker_side: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+pub type OrderbookResponse = Raw<OrderbookResponseFields>;
+
+/// Response of `GET /markets/{ticker}/orderbook`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderbookResponseFields {
+    #[serde(default)]
+    pub orderbook: Orderbook,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Resting bids on each side, as `[price_cents, count]` pairs sorted by price
+/// ascending. Kalshi only publishes bids; asks are implied by the other side.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Orderbook {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yes: Option<Vec<PriceLevel>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no: Option<Vec<PriceLevel>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "(i64, i64)", into = "(i64, i64)")]
+pub struct PriceLevel {
+    pub price: i64,
+    pub count: i64,
+}
+
+impl From<(i64, i64)> for PriceLevel {
+    fn from((price, count): (i64, i64)) -> Self {
+        Self { price, count }
+    }
+}
+
+impl From<PriceLevel> for (i64, i64) {
+    fn from(level: PriceLevel) -> Self {
+        (level.price, level.count)
+    }
+}
+
+/// One page of a cursor-paginated list endpoint.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub cursor: Option<String>,
+}
+
+/// A row type served by a cursor-paginated list endpoint.
+pub trait Paginated: DeserializeOwned + 'static {
+    const PATH: &'static str;
+    /// Key of the row array in the response envelope.
+    const KEY: &'static str;
+    const AUTH: bool;
+    /// Largest `limit` the endpoint accepts.
+    const MAX_PAGE_SIZE: usize;
+}
+
+impl Paginated for Market {
+    const PATH: &'static str = "/markets";
+    const KEY: &'static str = "markets";
+    const AUTH: bool = false;
+    const MAX_PAGE_SIZE: usize = 1000;
+}
+
+impl Paginated for Event {
+    const PATH: &'static str = "/events";
+    const KEY: &'static str = "events";
+    const AUTH: bool = false;
+    const MAX_PAGE_SIZE: usize = 200;
+}
+
+impl Paginated for Trade {
+    const PATH: &'static str = "/markets/trades";
+    const KEY: &'static str = "trades";
+    const AUTH: bool = false;
+    const MAX_PAGE_SIZE: usize = 1000;
+}
+
+impl Paginated for Order {
+    const PATH: &'static str = "/portfolio/orders";
+    const KEY: &'static str = "orders";
+    const AUTH: bool = true;
+    const MAX_PAGE_SIZE: usize = 200;
+}
+
+impl Paginated for Fill {
+    const PATH: &'static str = "/portfolio/fills";
+    const KEY: &'static str = "fills";
+    const AUTH: bool = true;
+    const MAX_PAGE_SIZE: usize = 200;
+}
+
+impl Paginated for Settlement {
+    const PATH: &'static str = "/portfolio/settlements";
+    const KEY: &'static str = "settlements";
+    const AUTH: bool = true;
+    const MAX_PAGE_SIZE: usize = 200;
+}
+
+impl Paginated for Position {
+    const PATH: &'static str = "/portfolio/positions";
+    const KEY: &'static str = "market_positions";
+    const AUTH: bool = true;
+    const MAX_PAGE_SIZE: usize = 200;
+}
+
+/// Reads an integer that the API may send as a number, a float or a numeric string.
+pub fn value_as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f.round() as i64)),
+        Value::String(s) => s
+            .parse::<i64>()
+            .ok()
+            .or_else(|| s.parse::<f64>().ok().map(|f| f.round() as i64)),
+        _ => None,
+    }
+}
+
+fn lenient_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(value_as_i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Market, OrderbookResponse};
+
+    #[test]
+    fn market_preserves_unknown_fields() {
+        let raw = json!({
+            "ticker": "KXTEST-1",
+            "yes_ask": 42,
+            "custom_strike": {"team": "NYK"},
+            "yes_ask_dollars": "0.4200"
+        });
+        let market: Market = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(market.yes_ask, Some(42));
+        assert_eq!(serde_json::to_value(&market).unwrap(), raw);
+    }
+
+    #[test]
+    fn market_accepts_numeric_strings() {
+        let raw = json!({
+            "ticker": "T",
+            "volume": "1200",
+            "open_interest": 3.6,
+            "floor_strike": 45,
+            "yes_ask": null,
+            "title": null,
+        });
+        let mut market: Market = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(market.volume, Some(1200));
+        assert_eq!(market.open_interest, Some(4));
+        assert_eq!(serde_json::to_value(&market).unwrap(), raw);
+
+        // Fields changed by the CLI are emitted as changed.
+        market.volume = Some(5);
+        market.open_interest = None;
+        market.event_market_count = Some(2);
+        assert_eq!(
+            serde_json::to_value(&market).unwrap(),
+            json!({
+                "ticker": "T",
+                "volume": 5,
+                "floor_strike": 45,
+                "yes_ask": null,
+                "title": null,
+                "event_market_count": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn orderbook_levels_round_trip() {
+        let raw = json!({"orderbook": {"yes": [[40, 10], [41, 5]], "no": null}});
+        let book: OrderbookResponse = serde_json::from_value(raw).unwrap();
+        let yes = book.orderbook.yes.clone().unwrap();
+        assert_eq!((yes[1].price, yes[1].count), (41, 5));
+        assert!(book.orderbook.no.is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::models::{Candlestick, Ohlc};
+
+use super::{
+    fmt_int, fmt_price, fmt_time, left, print_rows, right, standard_table, DisplayOptions,
+    OutputMode, PriceUnit,
+};
+
+const CSV_HEADER: &str = "end_period_ts,time,price_open,price_high,price_low,price_close,\
+yes_bid_open,yes_bid_high,yes_bid_low,yes_bid_close,\
+yes_ask_open,yes_ask_high,yes_ask_low,yes_ask_close,volume,open_interest";
+
+pub fn render_candles_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    candles: &[Candlestick],
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_rows(mode, display, candles, &[]);
+    }
+
+    let mut table = standard_table(
+        display,
+        &[
+            "Period End",
+            "Open",
+            "High",
+            "Low",
+            "Close",
+            "Yes Bid O/H/L/C",
+            "Yes Ask O/H/L/C",
+            "Volume",
+            "Open Int",
+        ],
+    );
+    for candle in candles {
+        let price = candle.price.as_ref();
+        table.add_row(vec![
+            left(fmt_time(display, Some(&period_end(candle)))),
+            right(fmt_price(
+                display,
+                price.and_then(|p| p.open),
+                PriceUnit::Cents,
+            )),
+            right(fmt_price(
+                display,
+                price.and_then(|p| p.high),
+                PriceUnit::Cents,
+            )),
+            right(fmt_price(
+                display,
+                price.and_then(|p| p.low),
+                PriceUnit::Cents,
+            )),
+            right(fmt_price(
+                display,
+                price.and_then(|p| p.close),
+                PriceUnit::Cents,
+            )),
+            right(ohlc_cell(display, candle.yes_bid.as_ref())),
+            right(ohlc_cell(display, candle.yes_ask.as_ref())),
+            right(fmt_int(candle.volume)),
+            right(fmt_int(candle.open_interest)),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// Prints candles as CSV with every OHLC series, prices in cents and empty
+/// cells for periods without data.
+pub fn print_candles_csv(candles: &[Candlestick]) {
+    println!("{CSV_HEADER}");
+    for candle in candles {
+        println!("{}", csv_row(candle));
+    }
+}
+
+fn csv_row(candle: &Candlestick) -> String {
+    let mut cells = vec![candle.end_period_ts.to_string(), period_end(candle)];
+    for ohlc in [&candle.price, &candle.yes_bid, &candle.yes_ask] {
+        let ohlc = ohlc.as_ref();
+        for value in [
+            ohlc.and_then(|o| o.open),
+            ohlc.and_then(|o| o.high),
+            ohlc.and_then(|o| o.low),
+            ohlc.and_then(|o| o.close),
+        ] {
+            cells.push(value.map(|v| v.to_string()).unwrap_or_default());
+        }
+    }
+    for value in [candle.volume, candle.open_interest] {
+        cells.push(value.map(|v| v.to_string()).unwrap_or_default());
+    }
+    cells.join(",")
+}
+
+fn ohlc_cell(display: &DisplayOptions, ohlc: Option<&Ohlc>) -> String {
+    let Some(o) = ohlc else {
+        return "-".to_string();
+    };
+    [o.open, o.high, o.low, o.close]
+        .iter()
+        .map(|v| fmt_price(display, *v, PriceUnit::Cents))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// RFC 3339 UTC time of the period end.
+fn period_end(candle: &Candlestick) -> String {
+    jiff::Timestamp::from_second(candle.end_period_ts)
+        .map(|ts| ts.to_string())
+        .unwrap_or_else(|_| candle.end_period_ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{csv_row, ohlc_cell, CSV_HEADER};
+    use crate::models::Candlestick;
+    use crate::output::{DisplayOptions, PriceUnit};
+
+    #[test]
+    fn writes_every_series_to_csv() {
+        let candle: Candlestick = serde_json::from_value(json!({
+            "end_period_ts": 1_700_000_000,
+            "price": {"open": null, "high": null, "low": null, "close": null},
+            "yes_bid": {"open": 40, "high": 45, "low": 39, "close": 44},
+            "yes_ask": {"open": "42", "high": 47, "low": 41, "close": 46},
+            "volume": 120,
+            "open_interest": 3400
+        }))
+        .unwrap();
+
+        let row = csv_row(&candle);
+        assert_eq!(
+            row,
+            "1700000000,2023-11-14T22:13:20Z,,,,,40,45,39,44,42,47,41,46,120,3400"
+        );
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+        let display = DisplayOptions::default();
+        assert_eq!(
+            ohlc_cell(&display, candle.yes_bid.as_ref()),
+            "40¢/45¢/39¢/44¢"
+        );
+        assert_eq!(ohlc_cell(&display, None), "-");
+        let dollars = DisplayOptions {
+            price_unit: Some(PriceUnit::Dollars),
+            ..DisplayOptions::default()
+        };
+        assert_eq!(
+            ohlc_cell(&dollars, candle.yes_ask.as_ref()),
+            "$0.42/$0.47/$0.41/$0.46"
+        );
+    }
+}
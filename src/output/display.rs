@@ -0,0 +1,54 @@
+use clap::ValueEnum;
+use comfy_table::{ContentArrangement, Table};
+use jiff::tz::TimeZone;
+use serde::{Deserialize, Serialize};
+
+/// How contract prices are shown in tables. JSON output is never converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceUnit {
+    /// `45¢`
+    Cents,
+    /// `$0.45`
+    Dollars,
+    /// `45%`, the implied probability
+    Percent,
+}
+
+/// Table formatting taken from the active profile's preferences, passed to
+/// renderers next to the output mode. `None` fields keep each renderer's
+/// built-in default.
+#[derive(Debug, Clone, Default)]
+pub struct DisplayOptions {
+    pub price_unit: Option<PriceUnit>,
+    pub timezone: Option<TimeZone>,
+    pub table_width: Option<u16>,
+    pub color: Option<bool>,
+}
+
+/// `utc`, `local` (the system zone) or an IANA name such as `America/New_York`.
+pub fn parse_timezone(name: &str) -> anyhow::Result<TimeZone> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "utc" | "z" => Ok(TimeZone::UTC),
+        "local" => Ok(TimeZone::system()),
+        _ => TimeZone::get(name.trim()).map_err(|_| {
+            anyhow::anyhow!("unknown timezone `{name}`; use utc, local or an IANA name")
+        }),
+    }
+}
+
+pub(super) fn apply_table_options(table: &mut Table, options: &DisplayOptions) {
+    if let Some(width) = options.table_width {
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_width(width);
+    }
+    match options.color {
+        Some(true) => {
+            table.enforce_styling();
+        }
+        Some(false) => {
+            table.force_no_tty();
+        }
+        None => {}
+    }
+}
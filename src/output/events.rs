@@ -1,22 +1,29 @@
-use serde_json::Value;
+use crate::models::{value_as_i64, Event};
 
-use super::{get_str, left, print_rows, standard_table, status_cell, truncate, OutputMode};
+use super::{
+    fmt_int, left, or_dash, print_rows, right, standard_table, status_cell, truncate,
+    DisplayOptions, OutputMode,
+};
 
 pub fn render_events_table(
     mode: OutputMode,
-    events: &[Value],
+    display: &DisplayOptions,
+    events: &[Event],
     fallback_status: Option<&str>,
 ) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_rows(mode, events, &[]);
+        return print_rows(mode, display, events, &[]);
     }
 
-    let mut table = standard_table(&["Event", "Category", "Series", "Market Cnt", "Status"]);
+    let mut table = standard_table(
+        display,
+        &["Event", "Category", "Series", "Market Cnt", "Status"],
+    );
     for event in events {
         table.add_row(vec![
-            left(truncate(get_str(event, "title"), 52)),
-            left(truncate(get_str(event, "category"), 14)),
-            left(truncate(get_str(event, "series_ticker"), 18)),
+            left(truncate(or_dash(event.title.as_deref()), 52)),
+            left(truncate(or_dash(event.category.as_deref()), 14)),
+            left(truncate(or_dash(event.series_ticker.as_deref()), 18)),
             left(event_market_count(event)),
             status_cell(event_status(event, fallback_status)),
         ]);
@@ -28,30 +35,34 @@ pub fn render_events_table(
 
 pub fn render_events_top_table(
     mode: OutputMode,
-    events: &[Value],
+    display: &DisplayOptions,
+    events: &[Event],
     fallback_status: Option<&str>,
 ) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_rows(mode, events, &[]);
+        return print_rows(mode, display, events, &[]);
     }
 
-    let mut table = standard_table(&[
-        "Event",
-        "Category",
-        "Series",
-        "Market Cnt",
-        "Total Vol",
-        "Open Int",
-        "Status",
-    ]);
+    let mut table = standard_table(
+        display,
+        &[
+            "Event",
+            "Category",
+            "Series",
+            "Market Cnt",
+            "Total Vol",
+            "Open Int",
+            "Status",
+        ],
+    );
     for event in events {
         table.add_row(vec![
-            left(truncate(get_str(event, "title"), 44)),
-            left(truncate(get_str(event, "category"), 14)),
-            left(truncate(get_str(event, "series_ticker"), 16)),
+            left(truncate(or_dash(event.title.as_deref()), 44)),
+            left(truncate(or_dash(event.category.as_deref()), 14)),
+            left(truncate(or_dash(event.series_ticker.as_deref()), 16)),
             left(event_market_count(event)),
-            super::right(super::fmt_int(super::get_i64(event, "total_volume"))),
-            super::right(super::fmt_int(super::get_i64(event, "open_interest"))),
+            right(fmt_int(event.total_volume)),
+            right(fmt_int(event.open_interest)),
             status_cell(event_status(event, fallback_status)),
         ]);
     }
@@ -60,29 +71,25 @@ pub fn render_events_top_table(
     Ok(())
 }
 
-fn event_status<'a>(event: &'a Value, fallback_status: Option<&'a str>) -> &'a str {
-    let status = get_str(event, "status");
-    if status == "-" || status.eq_ignore_ascii_case("null") || status.is_empty() {
-        return fallback_status.unwrap_or("-");
+fn event_status<'a>(event: &'a Event, fallback_status: Option<&'a str>) -> &'a str {
+    match event.status.as_deref() {
+        Some(status) if !status.is_empty() && !status.eq_ignore_ascii_case("null") => status,
+        _ => fallback_status.unwrap_or("-"),
     }
-    status
 }
 
-fn event_market_count(event: &Value) -> String {
-    if let Some(markets) = event.get("markets").and_then(Value::as_array) {
+fn event_market_count(event: &Event) -> String {
+    if let Some(markets) = &event.markets {
         return markets.len().to_string();
     }
 
-    for key in ["market_count", "markets_count", "num_markets"] {
-        if let Some(v) = event.get(key) {
-            if let Some(n) = v.as_i64() {
-                return n.to_string();
-            }
-            if let Some(s) = v.as_str() {
-                return s.to_string();
-            }
-        }
-    }
-
-    "-".to_string()
+    event
+        .market_count
+        .or_else(|| {
+            ["markets_count", "num_markets"]
+                .iter()
+                .find_map(|key| event.extra.get(*key).and_then(value_as_i64))
+        })
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "-".to_string())
 }
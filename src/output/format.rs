@@ -1,7 +1,5 @@
+use super::{right, DisplayOptions, PriceUnit};
 use comfy_table::{Attribute, Cell, Color};
-use serde_json::Value;
-
-use super::right;
 
 pub fn fmt_int(value: Option<i64>) -> String {
     let Some(v) = value else {
@@ -17,6 +15,107 @@ pub fn fmt_cents(value: Option<i64>) -> String {
     format!("{v}¢")
 }
 
+/// A contract price in cents, shown in the preferred unit or `default`.
+pub fn fmt_price(display: &DisplayOptions, cents: Option<i64>, default: PriceUnit) -> String {
+    let Some(v) = cents else {
+        return "-".to_string();
+    };
+    match display.price_unit.unwrap_or(default) {
+        PriceUnit::Cents => format!("{v}¢"),
+        PriceUnit::Percent => format!("{v}%"),
+        PriceUnit::Dollars => {
+            let sign = if v < 0 { "-" } else { "" };
+            format!("{sign}${}.{:02}", v.abs() / 100, v.abs() % 100)
+        }
+    }
+}
+
+/// Like [`fmt_price`] for derived prices such as a mid, to 0.1¢.
+pub fn fmt_price_f64(display: &DisplayOptions, cents: Option<f64>, default: PriceUnit) -> String {
+    let Some(v) = cents else {
+        return "-".to_string();
+    };
+    match display.price_unit.unwrap_or(default) {
+        PriceUnit::Cents => format!("{v:.1}¢"),
+        PriceUnit::Percent => format!("{v:.1}%"),
+        PriceUnit::Dollars => format!("${:.3}", v / 100.0),
+    }
+}
+
+/// Column header suffix for prices shown by [`fmt_price`].
+pub fn price_unit_label(display: &DisplayOptions, default: PriceUnit) -> &'static str {
+    match display.price_unit.unwrap_or(default) {
+        PriceUnit::Cents => "¢",
+        PriceUnit::Percent => "%",
+        PriceUnit::Dollars => "$",
+    }
+}
+
+/// An RFC 3339 API timestamp in the preferred timezone; unchanged when no
+/// timezone is configured or the value does not parse.
+pub fn fmt_time(display: &DisplayOptions, value: Option<&str>) -> String {
+    let raw = or_dash(value);
+    let Some(tz) = display.timezone.clone() else {
+        return raw.to_string();
+    };
+    match raw.parse::<jiff::Timestamp>() {
+        Ok(ts) => ts.to_zoned(tz).strftime("%Y-%m-%d %H:%M %Z").to_string(),
+        Err(_) => raw.to_string(),
+    }
+}
+
+/// An RFC 3339 API timestamp in the preferred timezone (the system zone when
+/// none is configured) with a countdown such as `in 2d 4h` or `3h 5m ago`.
+pub fn fmt_local_time(display: &DisplayOptions, value: Option<&str>) -> String {
+    let raw = or_dash(value);
+    let Ok(ts) = raw.parse::<jiff::Timestamp>() else {
+        return raw.to_string();
+    };
+    let tz = display
+        .timezone
+        .clone()
+        .unwrap_or_else(jiff::tz::TimeZone::system);
+    let delta = ts.as_second() - jiff::Timestamp::now().as_second();
+    format!(
+        "{} ({})",
+        ts.to_zoned(tz).strftime("%Y-%m-%d %H:%M %Z"),
+        fmt_countdown(delta)
+    )
+}
+
+/// A signed duration in seconds as its two largest units.
+pub fn fmt_countdown(delta_secs: i64) -> String {
+    let secs = delta_secs.abs();
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    let span = if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m")
+    } else {
+        return "now".to_string();
+    };
+    if delta_secs > 0 {
+        format!("in {span}")
+    } else {
+        format!("{span} ago")
+    }
+}
+
+/// An amount in cents as grouped dollars, e.g. `$12,345.67`.
+pub fn fmt_usd(cents: Option<i64>) -> String {
+    let Some(v) = cents else {
+        return "-".to_string();
+    };
+    let sign = if v < 0 { "-" } else { "" };
+    format!(
+        "{sign}${}.{:02}",
+        with_grouping(v.abs() / 100),
+        v.abs() % 100
+    )
+}
+
 pub fn status_cell(status: &str) -> Cell {
     let normalized = status.to_ascii_lowercase();
     match normalized.as_str() {
@@ -42,26 +141,12 @@ pub fn pnl_cell(value: Option<i64>) -> Cell {
     }
 }
 
-pub fn get_i64(row: &Value, key: &str) -> Option<i64> {
-    row.get(key).and_then(|v| match v {
-        Value::Number(n) => n.as_i64(),
-        Value::String(s) => s
-            .parse::<i64>()
-            .ok()
-            .or_else(|| s.parse::<f64>().ok().map(|f| f.round() as i64)),
-        _ => None,
-    })
-}
-
-pub fn get_str<'a>(row: &'a Value, key: &str) -> &'a str {
-    row.get(key).and_then(Value::as_str).unwrap_or("-")
-}
-
-pub fn extract_array(data: &Value, key: &str) -> Vec<Value> {
-    data.get(key)
-        .and_then(Value::as_array)
-        .cloned()
-        .unwrap_or_default()
+/// Display text for an optional API string, `-` when absent or empty.
+pub fn or_dash(value: Option<&str>) -> &str {
+    match value {
+        Some(v) if !v.is_empty() => v,
+        _ => "-",
+    }
 }
 
 fn with_grouping(value: i64) -> String {
@@ -69,7 +154,7 @@ fn with_grouping(value: i64) -> String {
     let digits = value.abs().to_string();
     let mut out = String::with_capacity(digits.len() + digits.len() / 3 + 1);
     for (idx, ch) in digits.chars().enumerate() {
-        if idx > 0 && (digits.len() - idx) % 3 == 0 {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
             out.push(',');
         }
         out.push(ch);
@@ -80,3 +165,17 @@ fn with_grouping(value: i64) -> String {
         out
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fmt_countdown, fmt_usd};
+
+    #[test]
+    fn formats_countdowns_and_dollars() {
+        assert_eq!(fmt_countdown(2 * 86_400 + 4 * 3_600 + 59), "in 2d 4h");
+        assert_eq!(fmt_countdown(-(3 * 3_600 + 5 * 60)), "3h 5m ago");
+        assert_eq!(fmt_countdown(30), "now");
+        assert_eq!(fmt_usd(Some(1_234_567)), "$12,345.67");
+        assert_eq!(fmt_usd(Some(-5)), "-$0.05");
+    }
+}
@@ -1,48 +1,45 @@
-use serde_json::Value;
+use comfy_table::Cell;
+
+use crate::models::{Event, Market, OrderbookResponse, PriceLevel, Series};
 
 use super::{
-    fmt_int, get_i64, get_str, left, print_rows, right, standard_table, status_cell, truncate,
-    OutputMode,
+    fmt_int, fmt_local_time, fmt_price, fmt_price_f64, fmt_usd, left, or_dash, price_unit_label,
+    print_rows, print_value, right, standard_table, status_cell, truncate, DisplayOptions,
+    OutputMode, PriceUnit,
 };
 
 const MAX_QUESTION_WIDTH: usize = 52;
+/// Width the detail view wraps to when there is no terminal to measure.
+const DETAIL_FALLBACK_WIDTH: u16 = 100;
 
 pub fn render_markets_table(
     mode: OutputMode,
-    markets: &[Value],
+    display: &DisplayOptions,
+    markets: &[Market],
     compact: bool,
 ) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_rows(mode, markets, &[]);
+        return print_rows(mode, display, markets, &[]);
     }
 
     if compact {
-        let mut table = standard_table(&["Question", "Contract", "Price (Yes)", "Vol", "Evt Mkts"]);
+        let mut table = standard_table(
+            display,
+            &["Question", "Contract", "Price (Yes)", "Vol", "Evt Mkts"],
+        );
         let mut prev_question: Option<String> = None;
         for market in markets {
-            let title = get_str(market, "title");
-            let question = if title == "-" {
-                get_str(market, "ticker")
-            } else {
-                title
-            };
-            let question = clean_question(question);
+            let question = clean_question(market_question(market));
             let show_question = match &prev_question {
                 Some(prev) if prev == question => "",
                 _ => question,
             };
             prev_question = Some(question.to_string());
 
-            let yes_price = fmt_percent(
-                get_i64(market, "yes_ask")
-                    .or_else(|| get_i64(market, "yes_bid"))
-                    .or_else(|| get_i64(market, "last_price")),
-            );
+            let yes_price = fmt_percent(display, market.yes_price());
             let contract = contract_label(market);
-            let vol = fmt_int(
-                get_i64(market, "open_volume").or_else(|| get_i64(market, "volume")),
-            );
-            let evt_mkts = fmt_int(get_i64(market, "event_market_count"));
+            let vol = fmt_int(market.open_volume.or(market.volume));
+            let evt_mkts = fmt_int(market.event_market_count);
 
             table.add_row(vec![
                 left(truncate(show_question, MAX_QUESTION_WIDTH)),
@@ -56,38 +53,32 @@ pub fn render_markets_table(
         return Ok(());
     }
 
-    let mut table = standard_table(&[
-        "Question",
-        "Contract",
-        "Price (Yes %)",
-        "Volume",
-        "Liquidity",
-        "Evt Mkts",
-        "Status",
-    ]);
+    let price_header = yes_price_header(display);
+    let mut table = standard_table(
+        display,
+        &[
+            "Question",
+            "Contract",
+            &price_header,
+            "Volume",
+            "Liquidity",
+            "Evt Mkts",
+            "Status",
+        ],
+    );
     let mut prev_question: Option<String> = None;
     for m in markets {
-        let title = get_str(m, "title");
-        let question = if title == "-" {
-            get_str(m, "ticker")
-        } else {
-            title
-        };
-        let question = clean_question(question);
+        let question = clean_question(market_question(m));
         let show_question = match &prev_question {
             Some(prev) if prev == question => "",
             _ => question,
         };
         prev_question = Some(question.to_string());
 
-        let yes_price = fmt_percent(
-            get_i64(m, "yes_ask")
-                .or_else(|| get_i64(m, "yes_bid"))
-                .or_else(|| get_i64(m, "last_price")),
-        );
-        let vol = fmt_int(get_i64(m, "volume"));
-        let liq = fmt_int(get_i64(m, "liquidity"));
-        let evt_mkts = fmt_int(get_i64(m, "event_market_count"));
+        let yes_price = fmt_percent(display, m.yes_price());
+        let vol = fmt_int(m.volume);
+        let liq = fmt_int(m.liquidity);
+        let evt_mkts = fmt_int(m.event_market_count);
         let contract = contract_label(m);
 
         table.add_row(vec![
@@ -97,50 +88,48 @@ pub fn render_markets_table(
             right(vol),
             right(liq),
             right(evt_mkts),
-            status_cell(get_str(m, "status")),
+            status_cell(or_dash(m.status.as_deref())),
         ]);
     }
     println!("{table}");
     Ok(())
 }
 
-pub fn render_markets_top_table(mode: OutputMode, markets: &[Value]) -> anyhow::Result<()> {
+pub fn render_markets_top_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    markets: &[Market],
+) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_rows(mode, markets, &[]);
+        return print_rows(mode, display, markets, &[]);
     }
 
-    let mut table = standard_table(&[
-        "Question",
-        "Contract",
-        "Price (Yes %)",
-        "Total Vol",
-        "Open Int",
-        "Evt Mkts",
-        "Status",
-    ]);
+    let price_header = yes_price_header(display);
+    let mut table = standard_table(
+        display,
+        &[
+            "Question",
+            "Contract",
+            &price_header,
+            "Total Vol",
+            "Open Int",
+            "Evt Mkts",
+            "Status",
+        ],
+    );
     let mut prev_question: Option<String> = None;
     for m in markets {
-        let title = get_str(m, "title");
-        let question = if title == "-" {
-            get_str(m, "ticker")
-        } else {
-            title
-        };
-        let question = clean_question(question);
+        let question = clean_question(market_question(m));
         let show_question = match &prev_question {
             Some(prev) if prev == question => "",
             _ => question,
         };
         prev_question = Some(question.to_string());
 
-        let yes_price = fmt_percent(
-            get_i64(m, "yes_ask")
-                .or_else(|| get_i64(m, "yes_bid"))
-                .or_else(|| get_i64(m, "last_price")),
-        );
-        let total_vol = fmt_int(get_i64(m, "volume"));
-        let open_int = fmt_int(get_i64(m, "open_interest"));
-        let evt_mkts = fmt_int(get_i64(m, "event_market_count"));
+        let yes_price = fmt_percent(display, m.yes_price());
+        let total_vol = fmt_int(m.volume);
+        let open_int = fmt_int(m.open_interest);
+        let evt_mkts = fmt_int(m.event_market_count);
         let contract = contract_label(m);
 
         table.add_row(vec![
@@ -150,13 +139,303 @@ pub fn render_markets_top_table(mode: OutputMode, markets: &[Value]) -> anyhow::
             right(total_vol),
             right(open_int),
             right(evt_mkts),
-            status_cell(get_str(m, "status")),
+            status_cell(or_dash(m.status.as_deref())),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// One market in full: contract wording, strike, quotes and activity,
+/// schedule in local time, settlement, parent event and series, and rules.
+/// `event` and `series` are optional lookups; JSON output stays
+/// `{"market": ...}`.
+pub fn render_market_detail(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    market: &Market,
+    event: Option<&Event>,
+    series: Option<&Series>,
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_value(mode, display, &serde_json::json!({ "market": market }));
+    }
+
+    let price = |cents| fmt_price(display, cents, PriceUnit::Cents);
+    let mut rows: Vec<(&str, Cell)> = vec![
+        ("Market", left(market.ticker.clone())),
+        ("Title", left(or_dash(market.title.as_deref()))),
+        ("Yes", left(or_dash(market.yes_sub_title.as_deref()))),
+        ("No", left(or_dash(market.no_sub_title.as_deref()))),
+        ("Status", status_cell(or_dash(market.status.as_deref()))),
+    ];
+    if let Some(strike) = strike_label(market) {
+        rows.push(("Strike", left(strike)));
+    }
+    rows.extend([
+        (
+            "Yes Bid / Ask",
+            left(format!(
+                "{} / {}",
+                price(market.yes_bid),
+                price(market.yes_ask)
+            )),
+        ),
+        (
+            "No Bid / Ask",
+            left(format!(
+                "{} / {}",
+                price(market.no_bid),
+                price(market.no_ask)
+            )),
+        ),
+        ("Last", left(price(market.last_price))),
+        (
+            "Volume",
+            left(format!(
+                "{} ({} in 24h)",
+                fmt_int(market.volume),
+                fmt_int(market.volume_24h)
+            )),
+        ),
+        ("Open Interest", left(fmt_int(market.open_interest))),
+        ("Liquidity", left(fmt_usd(market.liquidity))),
+        (
+            "Opens",
+            left(fmt_local_time(display, market.open_time.as_deref())),
+        ),
+        (
+            "Closes",
+            left(fmt_local_time(display, market.close_time.as_deref())),
+        ),
+        (
+            "Expires",
+            left(fmt_local_time(display, market.expiration_time.as_deref())),
+        ),
+    ]);
+    if let Some(result) = market.result.as_deref().filter(|r| !r.is_empty()) {
+        rows.push(("Result", left(result)));
+    }
+    rows.push((
+        "Event",
+        left(parent_label(
+            market.event_ticker.as_deref(),
+            event.and_then(|e| e.title.as_deref()),
+        )),
+    ));
+    let series_ticker = series
+        .map(|s| s.ticker.as_str())
+        .or_else(|| event.and_then(|e| e.series_ticker.as_deref()));
+    if series_ticker.is_some() {
+        let mut label = parent_label(series_ticker, series.and_then(|s| s.title.as_deref()));
+        if let Some(frequency) = series.and_then(|s| s.frequency.as_deref()) {
+            label.push_str(&format!(" ({frequency})"));
+        }
+        rows.push(("Series", left(label)));
+    }
+    for (field, rules) in [
+        ("Rules", &market.rules_primary),
+        ("Rules (cont.)", &market.rules_secondary),
+    ] {
+        if let Some(rules) = rules.as_deref().filter(|r| !r.trim().is_empty()) {
+            rows.push((field, left(rules.trim())));
+        }
+    }
+
+    let mut table = standard_table(display, &["Field", "Value"]);
+    if table.width().is_none() {
+        table.set_width(DETAIL_FALLBACK_WIDTH);
+    }
+    for (field, value) in rows {
+        table.add_row(vec![left(field), value]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// The settlement condition from `strike_type` and the floor/cap strikes.
+fn strike_label(market: &Market) -> Option<String> {
+    let (floor, cap) = (market.floor_strike, market.cap_strike);
+    let label = match (market.strike_type.as_deref(), floor, cap) {
+        (Some("between"), Some(floor), Some(cap)) => format!("between {floor} and {cap}"),
+        (Some("greater"), Some(floor), _) => format!("above {floor}"),
+        (Some("greater_or_equal"), Some(floor), _) => format!("{floor} or above"),
+        (Some("less"), _, Some(cap)) => format!("below {cap}"),
+        (Some("less_or_equal"), _, Some(cap)) => format!("{cap} or below"),
+        (kind, None, None) => return kind.map(str::to_string),
+        (kind, floor, cap) => {
+            let bound = |v: Option<f64>| v.map_or("-".to_string(), |v| v.to_string());
+            let bounds = format!("floor {}, cap {}", bound(floor), bound(cap));
+            match kind {
+                Some(kind) => format!("{kind}: {bounds}"),
+                None => bounds,
+            }
+        }
+    };
+    Some(label)
+}
+
+fn parent_label(ticker: Option<&str>, title: Option<&str>) -> String {
+    match (ticker, title) {
+        (Some(ticker), Some(title)) => format!("{ticker} — {title}"),
+        (ticker, title) => or_dash(ticker.or(title)).to_string(),
+    }
+}
+
+/// Two-sided YES ladder: YES bids against asks implied by NO bids (a NO bid
+/// at `p` is a YES offer at `100 - p`), best prices first, with cumulative
+/// size and a best bid/ask, spread, mid and microprice summary. JSON output
+/// stays the raw API response.
+pub fn render_orderbook(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    ticker: &str,
+    data: &OrderbookResponse,
+    depth: Option<usize>,
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_value(mode, display, data);
+    }
+
+    let ladder = Ladder::from_response(data, depth);
+    let summary = ladder.summary();
+
+    let mut table = standard_table(
+        display,
+        &[
+            "Market",
+            "Best Bid",
+            "Best Ask",
+            "Spread",
+            "Mid",
+            "Microprice",
+        ],
+    );
+    table.add_row(vec![
+        left(ticker),
+        right(level_label(display, ladder.bids.first())),
+        right(level_label(display, ladder.asks.first())),
+        right(fmt_price(display, summary.spread, PriceUnit::Cents)),
+        right(fmt_price_f64(display, summary.mid, PriceUnit::Cents)),
+        right(fmt_price_f64(display, summary.microprice, PriceUnit::Cents)),
+    ]);
+    println!("{table}");
+
+    if ladder.bids.is_empty() && ladder.asks.is_empty() {
+        println!("No resting orders");
+        return Ok(());
+    }
+
+    let mut table = standard_table(
+        display,
+        &["Bid Cum", "Bid Qty", "Bid", "Ask", "Ask Qty", "Ask Cum"],
+    );
+    let (mut bid_cum, mut ask_cum) = (0, 0);
+    for row in 0..ladder.bids.len().max(ladder.asks.len()) {
+        let bid = ladder.bids.get(row);
+        let ask = ladder.asks.get(row);
+        bid_cum += bid.map_or(0, |l| l.count);
+        ask_cum += ask.map_or(0, |l| l.count);
+        table.add_row(vec![
+            right(bid.map_or(String::new(), |_| fmt_int(Some(bid_cum)))),
+            right(bid.map_or(String::new(), |l| fmt_int(Some(l.count)))),
+            right(bid.map_or(String::new(), |l| {
+                fmt_price(display, Some(l.price), PriceUnit::Cents)
+            })),
+            right(ask.map_or(String::new(), |l| {
+                fmt_price(display, Some(l.price), PriceUnit::Cents)
+            })),
+            right(ask.map_or(String::new(), |l| fmt_int(Some(l.count)))),
+            right(ask.map_or(String::new(), |_| fmt_int(Some(ask_cum)))),
         ]);
     }
     println!("{table}");
     Ok(())
 }
 
+/// YES bids and implied YES asks, each sorted best price first.
+struct Ladder {
+    bids: Vec<PriceLevel>,
+    asks: Vec<PriceLevel>,
+}
+
+struct BookSummary {
+    spread: Option<i64>,
+    mid: Option<f64>,
+    /// Mid weighted by the opposite side's size at the touch.
+    microprice: Option<f64>,
+}
+
+impl Ladder {
+    fn from_response(data: &OrderbookResponse, depth: Option<usize>) -> Self {
+        let mut bids = data
+            .orderbook
+            .yes
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|level| level.count > 0)
+            .collect::<Vec<_>>();
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+
+        let mut asks = data
+            .orderbook
+            .no
+            .iter()
+            .flatten()
+            .filter(|level| level.count > 0)
+            .map(|level| PriceLevel {
+                price: 100 - level.price,
+                count: level.count,
+            })
+            .collect::<Vec<_>>();
+        asks.sort_by_key(|level| level.price);
+
+        if let Some(depth) = depth {
+            bids.truncate(depth);
+            asks.truncate(depth);
+        }
+        Self { bids, asks }
+    }
+
+    fn summary(&self) -> BookSummary {
+        let (Some(bid), Some(ask)) = (self.bids.first(), self.asks.first()) else {
+            return BookSummary {
+                spread: None,
+                mid: None,
+                microprice: None,
+            };
+        };
+        let (bid_price, ask_price) = (bid.price as f64, ask.price as f64);
+        let size = bid.count + ask.count;
+        BookSummary {
+            spread: Some(ask.price - bid.price),
+            mid: Some((bid_price + ask_price) / 2.0),
+            microprice: (size > 0).then(|| {
+                (bid_price * ask.count as f64 + ask_price * bid.count as f64) / size as f64
+            }),
+        }
+    }
+}
+
+fn level_label(display: &DisplayOptions, level: Option<&PriceLevel>) -> String {
+    match level {
+        Some(l) => format!(
+            "{} x {}",
+            fmt_price(display, Some(l.price), PriceUnit::Cents),
+            fmt_int(Some(l.count))
+        ),
+        None => "-".to_string(),
+    }
+}
+
+fn market_question(market: &Market) -> &str {
+    match market.title.as_deref() {
+        Some(title) if !title.is_empty() => title,
+        _ => &market.ticker,
+    }
+}
+
 fn clean_question(input: &str) -> &str {
     let trimmed = input.trim_start();
     if let Some(rest) = trimmed.strip_prefix("yes ") {
@@ -174,25 +453,89 @@ fn clean_question(input: &str) -> &str {
     trimmed
 }
 
-fn fmt_percent(cents: Option<i64>) -> String {
-    match cents {
-        Some(v) => format!("{v}%"),
-        None => "-".to_string(),
-    }
+fn fmt_percent(display: &DisplayOptions, cents: Option<i64>) -> String {
+    fmt_price(display, cents, PriceUnit::Percent)
+}
+
+fn yes_price_header(display: &DisplayOptions) -> String {
+    format!(
+        "Price (Yes {})",
+        price_unit_label(display, PriceUnit::Percent)
+    )
 }
 
-fn contract_label(market: &Value) -> String {
-    for key in ["yes_sub_title", "subtitle", "no_sub_title"] {
-        let v = get_str(market, key);
-        if v != "-" && !v.is_empty() {
+fn contract_label(market: &Market) -> String {
+    for label in [
+        &market.yes_sub_title,
+        &market.subtitle,
+        &market.no_sub_title,
+    ] {
+        if let Some(v) = label.as_deref().filter(|v| !v.is_empty()) {
             return clean_question(v).to_string();
         }
     }
 
-    let exp = get_str(market, "expiration_time");
-    if exp != "-" && exp.len() >= 10 {
+    if let Some(exp) = market.expiration_time.as_deref().filter(|e| e.len() >= 10) {
         return format!("Exp {}", &exp[..10]);
     }
 
-    get_str(market, "ticker").to_string()
+    market.ticker.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{strike_label, Ladder};
+    use crate::models::{Market, MarketFields, OrderbookResponse};
+
+    #[test]
+    fn implies_yes_asks_from_no_bids() {
+        let data: OrderbookResponse = serde_json::from_value(json!({
+            "orderbook": {"yes": [[40, 10], [42, 30]], "no": [[50, 5], [55, 10]]}
+        }))
+        .unwrap();
+
+        let ladder = Ladder::from_response(&data, None);
+        assert_eq!(ladder.bids[0].price, 42);
+        assert_eq!((ladder.asks[0].price, ladder.asks[0].count), (45, 10));
+        assert_eq!(ladder.asks[1].price, 50);
+
+        let summary = ladder.summary();
+        assert_eq!(summary.spread, Some(3));
+        assert_eq!(summary.mid, Some(43.5));
+        // 42 * 10 + 45 * 30 over 40 contracts: heavier bid pulls toward the ask.
+        assert_eq!(summary.microprice, Some(44.25));
+
+        let shallow = Ladder::from_response(&data, Some(1));
+        assert_eq!((shallow.bids.len(), shallow.asks.len()), (1, 1));
+    }
+
+    #[test]
+    fn describes_strikes() {
+        let mut market = Market::from(MarketFields {
+            strike_type: Some("between".to_string()),
+            floor_strike: Some(45.0),
+            cap_strike: Some(46.5),
+            ..MarketFields::default()
+        });
+        assert_eq!(
+            strike_label(&market).as_deref(),
+            Some("between 45 and 46.5")
+        );
+
+        market.strike_type = Some("greater".to_string());
+        assert_eq!(strike_label(&market).as_deref(), Some("above 45"));
+
+        market.strike_type = Some("custom".to_string());
+        market.floor_strike = None;
+        assert_eq!(
+            strike_label(&market).as_deref(),
+            Some("custom: floor -, cap 46.5")
+        );
+
+        market.cap_strike = None;
+        market.strike_type = None;
+        assert_eq!(strike_label(&market), None);
+    }
 }
@@ -1,49 +1,79 @@
+mod candles;
+mod display;
 mod events;
 mod format;
 mod markets;
 mod orders;
 mod portfolio;
+mod series;
 mod table;
+mod trades;
 
+pub use candles::{print_candles_csv, render_candles_table};
+pub use display::{parse_timezone, DisplayOptions, PriceUnit};
 pub use events::{render_events_table, render_events_top_table};
-pub use format::{extract_array, fmt_cents, fmt_int, get_i64, get_str, pnl_cell, status_cell};
-pub use markets::{render_markets_table, render_markets_top_table};
+pub use format::{
+    fmt_cents, fmt_int, fmt_local_time, fmt_price, fmt_price_f64, fmt_time, fmt_usd, or_dash,
+    pnl_cell, price_unit_label, status_cell,
+};
+pub use markets::{
+    render_market_detail, render_markets_table, render_markets_top_table, render_orderbook,
+};
 pub use orders::render_order_table;
-pub use portfolio::{render_balance_table, render_positions_table};
+pub use portfolio::{
+    render_balance_table, render_fills_table, render_positions_table, render_settlements_table,
+};
+pub use series::{render_series_detail, render_series_next, render_series_table};
 pub use table::{left, right, standard_table, truncate};
+pub use trades::render_trades_table;
 
 use comfy_table::{presets::UTF8_FULL, Cell, Table};
+use display::apply_table_options;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputMode {
+    #[default]
     Table,
     Json,
 }
 
-pub fn print_value(mode: OutputMode, value: &Value) -> anyhow::Result<()> {
+pub fn print_value<T: Serialize + ?Sized>(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    value: &T,
+) -> anyhow::Result<()> {
+    let value = serde_json::to_value(value)?;
     match mode {
         OutputMode::Json => {
-            println!("{}", serde_json::to_string_pretty(value)?);
+            println!("{}", serde_json::to_string_pretty(&value)?);
         }
         OutputMode::Table => {
             if let Some(obj) = value.as_object() {
                 let mut table = Table::new();
                 table.load_preset(UTF8_FULL);
                 table.set_header(vec!["Field", "Value"]);
+                apply_table_options(&mut table, display);
                 for (k, v) in obj {
                     table.add_row(vec![Cell::new(k), Cell::new(short_json(v))]);
                 }
                 println!("{table}");
             } else {
-                println!("{}", serde_json::to_string_pretty(value)?);
+                println!("{}", serde_json::to_string_pretty(&value)?);
             }
         }
     }
     Ok(())
 }
 
-pub fn print_rows(mode: OutputMode, rows: &[Value], columns: &[&str]) -> anyhow::Result<()> {
+pub fn print_rows<T: Serialize>(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    rows: &[T],
+    columns: &[&str],
+) -> anyhow::Result<()> {
     match mode {
         OutputMode::Json => {
             println!("{}", serde_json::to_string_pretty(rows)?);
@@ -52,12 +82,14 @@ pub fn print_rows(mode: OutputMode, rows: &[Value], columns: &[&str]) -> anyhow:
             let mut table = Table::new();
             table.load_preset(UTF8_FULL);
             table.set_header(columns.iter().map(|c| Cell::new(*c)).collect::<Vec<_>>());
+            apply_table_options(&mut table, display);
 
             for row in rows {
+                let row = serde_json::to_value(row)?;
                 table.add_row(
                     columns
                         .iter()
-                        .map(|c| Cell::new(read_column(row, c)))
+                        .map(|c| Cell::new(read_column(&row, c)))
                         .collect::<Vec<_>>(),
                 );
             }
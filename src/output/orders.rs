@@ -1,70 +1,86 @@
-use serde_json::Value;
+use crate::models::Order;
 
 use super::{
-    fmt_cents, fmt_int, get_i64, get_str, left, print_rows, right, standard_table, status_cell,
-    truncate, OutputMode,
+    fmt_int, fmt_price, fmt_time, left, or_dash, print_rows, right, standard_table, status_cell,
+    truncate, DisplayOptions, OutputMode, PriceUnit,
 };
 
 const ORDER_ID_WIDTH: usize = 14;
 
-pub fn render_order_table(mode: OutputMode, rows: &[Value], compact: bool) -> anyhow::Result<()> {
+pub fn render_order_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    rows: &[Order],
+    compact: bool,
+) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_rows(mode, rows, &[]);
+        return print_rows(mode, display, rows, &[]);
     }
 
     if compact {
-        let mut table = standard_table(&["Order ID", "Ticker", "Side", "Price", "Count", "Status"]);
+        let mut table = standard_table(
+            display,
+            &["Order ID", "Ticker", "Side", "Price", "Count", "Status"],
+        );
         for row in rows {
             table.add_row(vec![
-                left(truncate(get_str(row, "order_id"), ORDER_ID_WIDTH)),
-                left(truncate(get_str(row, "ticker"), 26)),
-                left(get_str(row, "side")),
-                right(order_price(row)),
-                right(order_count(row, "count", "count_fp")),
-                status_cell(get_str(row, "status")),
+                left(truncate(or_dash(Some(&row.order_id)), ORDER_ID_WIDTH)),
+                left(truncate(or_dash(row.ticker.as_deref()), 26)),
+                left(or_dash(row.side.as_deref())),
+                right(order_price(display, row)),
+                right(order_count(row.count, row.count_fp.as_deref())),
+                status_cell(or_dash(row.status.as_deref())),
             ]);
         }
         println!("{table}");
         return Ok(());
     }
 
-    let mut table = standard_table(&[
-        "Order ID", "Ticker", "Side", "Action", "Price", "Count", "Remain", "Status", "Created",
-    ]);
+    let mut table = standard_table(
+        display,
+        &[
+            "Order ID", "Ticker", "Side", "Action", "Price", "Count", "Remain", "Status", "Created",
+        ],
+    );
     for row in rows {
         table.add_row(vec![
-            left(truncate(get_str(row, "order_id"), ORDER_ID_WIDTH)),
-            left(truncate(get_str(row, "ticker"), 30)),
-            left(get_str(row, "side")),
-            left(get_str(row, "action")),
-            right(order_price(row)),
-            right(order_count(row, "count", "count_fp")),
-            right(order_count(row, "remaining_count", "remaining_count_fp")),
-            status_cell(get_str(row, "status")),
-            left(truncate(get_str(row, "created_time"), 20)),
+            left(truncate(or_dash(Some(&row.order_id)), ORDER_ID_WIDTH)),
+            left(truncate(or_dash(row.ticker.as_deref()), 30)),
+            left(or_dash(row.side.as_deref())),
+            left(or_dash(row.action.as_deref())),
+            right(order_price(display, row)),
+            right(order_count(row.count, row.count_fp.as_deref())),
+            right(order_count(
+                row.remaining_count,
+                row.remaining_count_fp.as_deref(),
+            )),
+            status_cell(or_dash(row.status.as_deref())),
+            left(truncate(
+                &fmt_time(display, row.created_time.as_deref()),
+                20,
+            )),
         ]);
     }
     println!("{table}");
     Ok(())
 }
 
-fn order_price(row: &Value) -> String {
-    let side = get_str(row, "side");
-    let cents = if side.eq_ignore_ascii_case("no") {
-        get_i64(row, "no_price").or_else(|| get_i64(row, "yes_price"))
+fn order_price(display: &DisplayOptions, row: &Order) -> String {
+    let is_no = row
+        .side
+        .as_deref()
+        .is_some_and(|side| side.eq_ignore_ascii_case("no"));
+    let cents = if is_no {
+        row.no_price.or(row.yes_price)
     } else {
-        get_i64(row, "yes_price").or_else(|| get_i64(row, "no_price"))
+        row.yes_price.or(row.no_price)
     };
-    fmt_cents(cents)
+    fmt_price(display, cents, PriceUnit::Cents)
 }
 
-fn order_count(row: &Value, int_key: &str, fp_key: &str) -> String {
-    if let Some(v) = get_i64(row, int_key) {
+fn order_count(count: Option<i64>, count_fp: Option<&str>) -> String {
+    if let Some(v) = count {
         return fmt_int(Some(v));
     }
-    let fp = get_str(row, fp_key);
-    if fp != "-" {
-        return fp.to_string();
-    }
-    "-".to_string()
+    or_dash(count_fp).to_string()
 }
@@ -1,21 +1,34 @@
 use serde_json::Value;
 
+use crate::models::{value_as_i64, Balance, Fill, Position, Settlement};
+
 use super::{
-    fmt_int, get_i64, get_str, left, pnl_cell, print_rows, print_value, right, standard_table,
-    status_cell, truncate, OutputMode,
+    fmt_cents, fmt_int, fmt_price, fmt_time, left, or_dash, pnl_cell, print_rows, print_value,
+    right, standard_table, status_cell, truncate, DisplayOptions, OutputMode, PriceUnit,
 };
 
-pub fn render_balance_table(mode: OutputMode, data: &Value) -> anyhow::Result<()> {
+pub fn render_balance_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    data: &Balance,
+) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_value(mode, data);
+        return print_value(mode, display, data);
     }
 
-    let Some(obj) = data.as_object() else {
-        return print_value(mode, data);
-    };
-
-    let mut table = standard_table(&["Field", "Value"]);
-    for (key, value) in obj {
+    let mut table = standard_table(display, &["Field", "Value"]);
+    for (key, cents) in [
+        ("balance", data.balance),
+        ("portfolio_value", data.portfolio_value),
+    ] {
+        if let Some(cents) = cents {
+            table.add_row(vec![
+                left(key),
+                right(balance_cell_value(key, &cents.into())),
+            ]);
+        }
+    }
+    for (key, value) in &data.extra {
         if !should_show_balance_field(key) {
             continue;
         }
@@ -27,25 +40,26 @@ pub fn render_balance_table(mode: OutputMode, data: &Value) -> anyhow::Result<()
 
 pub fn render_positions_table(
     mode: OutputMode,
-    rows: &[Value],
+    display: &DisplayOptions,
+    rows: &[Position],
     compact: bool,
 ) -> anyhow::Result<()> {
     if mode == OutputMode::Json {
-        return print_rows(mode, rows, &[]);
+        return print_rows(mode, display, rows, &[]);
     }
 
     if compact {
-        let mut table = standard_table(&["Ticker", "Position", "PnL", "Status"]);
+        let mut table = standard_table(display, &["Ticker", "Position", "PnL", "Status"]);
         for row in rows {
-            let status = row
-                .get("market_result")
-                .and_then(Value::as_str)
-                .map(|_| "settled")
-                .unwrap_or("open");
+            let status = if row.market_result.is_some() {
+                "settled"
+            } else {
+                "open"
+            };
             table.add_row(vec![
-                left(truncate(get_str(row, "ticker"), 30)),
-                right(fmt_int(get_i64(row, "position"))),
-                pnl_cell(get_i64(row, "realized_pnl")),
+                left(truncate(&row.ticker, 30)),
+                right(fmt_int(row.position)),
+                pnl_cell(row.realized_pnl),
                 status_cell(status),
             ]);
         }
@@ -53,15 +67,81 @@ pub fn render_positions_table(
         return Ok(());
     }
 
-    let mut table = standard_table(&["Ticker", "Market", "Pos", "PnL", "Fees", "Event"]);
+    let mut table = standard_table(
+        display,
+        &["Ticker", "Market", "Pos", "PnL", "Fees", "Event"],
+    );
     for row in rows {
         table.add_row(vec![
-            left(truncate(get_str(row, "ticker"), 28)),
-            left(truncate(get_str(row, "market_title"), 44)),
-            right(fmt_int(get_i64(row, "position"))),
-            pnl_cell(get_i64(row, "realized_pnl")),
-            right(fmt_int(get_i64(row, "fees_paid"))),
-            left(truncate(get_str(row, "event_title"), 36)),
+            left(truncate(&row.ticker, 28)),
+            left(truncate(or_dash(row.market_title.as_deref()), 44)),
+            right(fmt_int(row.position)),
+            pnl_cell(row.realized_pnl),
+            right(fmt_int(row.fees_paid)),
+            left(truncate(or_dash(row.event_title.as_deref()), 36)),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// Fills with the price of the side traded.
+pub fn render_fills_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    rows: &[Fill],
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_rows(mode, display, rows, &[]);
+    }
+
+    let mut table = standard_table(
+        display,
+        &[
+            "Trade ID", "Ticker", "Side", "Action", "Count", "Price", "Time",
+        ],
+    );
+    for row in rows {
+        let price = match row.side.as_deref() {
+            Some(side) if side.eq_ignore_ascii_case("no") => row.no_price,
+            _ => row.yes_price,
+        };
+        table.add_row(vec![
+            left(truncate(&row.trade_id, 14)),
+            left(truncate(or_dash(row.ticker.as_deref()), 30)),
+            left(or_dash(row.side.as_deref())),
+            left(or_dash(row.action.as_deref())),
+            right(fmt_int(row.count)),
+            right(fmt_price(display, price, PriceUnit::Cents)),
+            left(fmt_time(display, row.created_time.as_deref())),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+pub fn render_settlements_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    rows: &[Settlement],
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_rows(mode, display, rows, &[]);
+    }
+
+    let mut table = standard_table(
+        display,
+        &["Ticker", "Result", "Yes", "No", "Revenue", "PnL", "Settled"],
+    );
+    for row in rows {
+        table.add_row(vec![
+            left(truncate(&row.ticker, 30)),
+            left(or_dash(row.market_result.as_deref())),
+            right(fmt_int(row.yes_count)),
+            right(fmt_int(row.no_count)),
+            right(fmt_cents(row.revenue)),
+            pnl_cell(row.realized_pnl),
+            left(fmt_time(display, row.settled_time.as_deref())),
         ]);
     }
     println!("{table}");
@@ -80,7 +160,7 @@ fn should_show_balance_field(key: &str) -> bool {
 }
 
 fn fmt_dollars(value: &Value) -> String {
-    let Some(cents) = value_to_cents(value) else {
+    let Some(cents) = value_as_i64(value) else {
         return scalar_to_string(value);
     };
     let sign = if cents < 0 { "-" } else { "" };
@@ -88,19 +168,6 @@ fn fmt_dollars(value: &Value) -> String {
     format!("{sign}${}.{:02}", abs / 100, abs % 100)
 }
 
-fn value_to_cents(value: &Value) -> Option<i64> {
-    match value {
-        Value::Number(n) => n
-            .as_i64()
-            .or_else(|| n.as_f64().map(|f| f.round() as i64)),
-        Value::String(s) => s
-            .parse::<i64>()
-            .ok()
-            .or_else(|| s.parse::<f64>().ok().map(|f| f.round() as i64)),
-        _ => None,
-    }
-}
-
 fn scalar_to_string(value: &Value) -> String {
     match value {
         Value::Null => "-".to_string(),
@@ -120,12 +187,18 @@ mod tests {
     #[test]
     fn formats_balance_in_dollars() {
         assert_eq!(balance_cell_value("balance", &json!(1110)), "$11.10");
-        assert_eq!(balance_cell_value("portfolio_value", &json!("15007")), "$150.07");
+        assert_eq!(
+            balance_cell_value("portfolio_value", &json!("15007")),
+            "$150.07"
+        );
     }
 
     #[test]
     fn leaves_non_balance_fields_unchanged() {
-        assert_eq!(balance_cell_value("updated_ts", &json!(1771987869)), "1771987869");
+        assert_eq!(
+            balance_cell_value("updated_ts", &json!(1771987869)),
+            "1771987869"
+        );
     }
 
     #[test]
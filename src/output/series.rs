@@ -0,0 +1,162 @@
+use crate::models::{Event, Market, Series};
+
+use super::{
+    fmt_time, left, or_dash, print_rows, print_value, render_markets_table, right, standard_table,
+    truncate, DisplayOptions, OutputMode,
+};
+
+pub fn render_series_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    series: &[Series],
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_rows(mode, display, series, &[]);
+    }
+
+    let mut table = standard_table(
+        display,
+        &["Series", "Title", "Category", "Frequency", "Tags"],
+    );
+    for s in series {
+        table.add_row(vec![
+            left(truncate(&s.ticker, 20)),
+            left(truncate(or_dash(s.title.as_deref()), 48)),
+            left(truncate(or_dash(s.category.as_deref()), 16)),
+            left(or_dash(s.frequency.as_deref())),
+            left(truncate(&join_tags(s), 32)),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+pub fn render_series_detail(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    series: &Series,
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_value(mode, display, series);
+    }
+
+    let sources = series
+        .settlement_sources
+        .iter()
+        .flatten()
+        .map(
+            |source| match (source.name.as_deref(), source.url.as_deref()) {
+                (Some(name), Some(url)) => format!("{name} ({url})"),
+                (Some(name), None) => name.to_string(),
+                (None, Some(url)) => url.to_string(),
+                (None, None) => "-".to_string(),
+            },
+        )
+        .collect::<Vec<_>>();
+
+    let mut table = standard_table(display, &["Field", "Value"]);
+    for (field, value) in [
+        ("Series", series.ticker.clone()),
+        ("Title", or_dash(series.title.as_deref()).to_string()),
+        ("Category", or_dash(series.category.as_deref()).to_string()),
+        (
+            "Frequency",
+            or_dash(series.frequency.as_deref()).to_string(),
+        ),
+        ("Tags", join_tags(series)),
+        (
+            "Settlement",
+            if sources.is_empty() {
+                "-".to_string()
+            } else {
+                sources.join("\n")
+            },
+        ),
+        ("Fees", fee_schedule(series)),
+        (
+            "Contract Terms",
+            or_dash(series.contract_url.as_deref()).to_string(),
+        ),
+    ] {
+        table.add_row(vec![left(field), left(value)]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// The next event of a series followed by its markets.
+pub fn render_series_next(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    event: &Event,
+    markets: &[Market],
+    close_ts: Option<i64>,
+    compact: bool,
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_value(
+            mode,
+            display,
+            &serde_json::json!({ "event": event, "markets": markets }),
+        );
+    }
+
+    let closes = close_ts
+        .and_then(|ts| jiff::Timestamp::from_second(ts).ok())
+        .map(|ts| ts.to_string());
+    let mut table = standard_table(display, &["Event", "Title", "Closes", "Markets"]);
+    table.add_row(vec![
+        left(event.event_ticker.clone()),
+        left(truncate(or_dash(event.title.as_deref()), 56)),
+        left(fmt_time(display, closes.as_deref())),
+        right(markets.len().to_string()),
+    ]);
+    println!("{table}");
+    render_markets_table(mode, display, markets, compact)
+}
+
+fn join_tags(series: &Series) -> String {
+    match series.tags.as_deref() {
+        Some(tags) if !tags.is_empty() => tags.join(", "),
+        _ => "-".to_string(),
+    }
+}
+
+/// Fee type and multiplier, with the per-contract fee at 50¢ (the maximum)
+/// for the quadratic schedules: `multiplier × P × (1 − P)`.
+fn fee_schedule(series: &Series) -> String {
+    match (series.fee_type.as_deref(), series.fee_multiplier) {
+        (Some(kind), Some(multiplier)) if kind.starts_with("quadratic") => format!(
+            "{kind}, multiplier {multiplier} (max {:.2}¢/contract at 50¢)",
+            multiplier * 25.0
+        ),
+        (Some(kind), Some(multiplier)) => format!("{kind}, multiplier {multiplier}"),
+        (Some(kind), None) => kind.to_string(),
+        (None, Some(multiplier)) => format!("multiplier {multiplier}"),
+        (None, None) => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fee_schedule;
+    use crate::models::{Series, SeriesFields};
+
+    #[test]
+    fn describes_fee_schedule() {
+        let mut series = Series::from(SeriesFields {
+            fee_type: Some("quadratic".to_string()),
+            fee_multiplier: Some(0.07),
+            ..SeriesFields::default()
+        });
+        assert_eq!(
+            fee_schedule(&series),
+            "quadratic, multiplier 0.07 (max 1.75¢/contract at 50¢)"
+        );
+
+        series.fee_type = Some("flat".to_string());
+        assert_eq!(fee_schedule(&series), "flat, multiplier 0.07");
+        series.fee_multiplier = None;
+        assert_eq!(fee_schedule(&series), "flat");
+    }
+}
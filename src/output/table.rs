@@ -1,6 +1,8 @@
 use comfy_table::{presets::UTF8_FULL, Attribute, Cell, CellAlignment, ContentArrangement, Table};
 
-pub fn standard_table(headers: &[&str]) -> Table {
+use super::{display::apply_table_options, DisplayOptions};
+
+pub fn standard_table(display: &DisplayOptions, headers: &[&str]) -> Table {
     let mut table = Table::new();
     table.load_preset(UTF8_FULL);
     table.set_content_arrangement(ContentArrangement::Dynamic);
@@ -10,6 +12,7 @@ pub fn standard_table(headers: &[&str]) -> Table {
             .map(|h| Cell::new(*h).add_attribute(Attribute::Bold))
             .collect::<Vec<_>>(),
     );
+    apply_table_options(&mut table, display);
     table
 }
 
@@ -0,0 +1,34 @@
+use crate::models::Trade;
+
+use super::{
+    fmt_int, fmt_price, fmt_time, left, or_dash, print_rows, right, standard_table, truncate,
+    DisplayOptions, OutputMode, PriceUnit,
+};
+
+pub fn render_trades_table(
+    mode: OutputMode,
+    display: &DisplayOptions,
+    rows: &[Trade],
+) -> anyhow::Result<()> {
+    if mode == OutputMode::Json {
+        return print_rows(mode, display, rows, &[]);
+    }
+
+    let mut table = standard_table(
+        display,
+        &["Trade ID", "Ticker", "Taker", "Count", "Yes", "No", "Time"],
+    );
+    for row in rows {
+        table.add_row(vec![
+            left(truncate(&row.trade_id, 14)),
+            left(truncate(or_dash(row.ticker.as_deref()), 30)),
+            left(or_dash(row.taker_side.as_deref())),
+            right(fmt_int(row.count)),
+            right(fmt_price(display, row.yes_price, PriceUnit::Cents)),
+            right(fmt_price(display, row.no_price, PriceUnit::Cents)),
+            left(fmt_time(display, row.created_time.as_deref())),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
@@ -16,10 +16,6 @@ impl QueryParams {
         self
     }
 
-    pub fn limit(self, n: usize) -> Self {
-        self.insert("limit", n)
-    }
-
     pub fn optional<T>(self, key: &str, value: Option<T>) -> Self
     where
         T: ToString,
@@ -0,0 +1,257 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+/// Requests per second allowed for reads (GET) and writes (POST/DELETE).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub read_per_sec: f64,
+    pub write_per_sec: f64,
+}
+
+impl RateLimit {
+    /// Kalshi's Basic tier, the default for new API keys.
+    pub const BASIC: Self = Self::new(20.0, 10.0);
+    pub const ADVANCED: Self = Self::new(30.0, 30.0);
+    pub const PREMIER: Self = Self::new(100.0, 100.0);
+    pub const PRIME: Self = Self::new(400.0, 400.0);
+
+    const fn new(read_per_sec: f64, write_per_sec: f64) -> Self {
+        Self {
+            read_per_sec,
+            write_per_sec,
+        }
+    }
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self::BASIC
+    }
+}
+
+/// Slowest accepted rate (one request every ~17 minutes); smaller rates
+/// would overflow the refill wait.
+const MIN_RATE: f64 = 0.001;
+
+/// Parses `--rate-limit` values: a tier name (`basic`, `advanced`, `premier`,
+/// `prime`), `off`, a single rate for both budgets (`15`), or `READ/WRITE`
+/// (`20/10`). A rate of `0` disables that budget.
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let value = input.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "basic" => return Ok(Self::BASIC),
+            "advanced" => return Ok(Self::ADVANCED),
+            "premier" => return Ok(Self::PREMIER),
+            "prime" => return Ok(Self::PRIME),
+            "off" | "none" => return Ok(Self::new(0.0, 0.0)),
+            _ => {}
+        }
+
+        let parse = |s: &str| {
+            let n = s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite() && *n >= 0.0)
+                .ok_or_else(|| {
+                    format!(
+                        "invalid rate limit `{input}`: expected a tier (basic, advanced, premier, prime), `off`, N or READ/WRITE"
+                    )
+                })?;
+            if n > 0.0 && n < MIN_RATE {
+                return Err(format!(
+                    "invalid rate limit `{input}`: rates must be 0 (off) or at least {MIN_RATE} per second"
+                ));
+            }
+            Ok(n)
+        };
+
+        match value.split_once('/') {
+            Some((read, write)) => Ok(Self::new(parse(read)?, parse(write)?)),
+            None => {
+                let n = parse(&value)?;
+                Ok(Self::new(n, n))
+            }
+        }
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.read_per_sec, self.write_per_sec)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    Read,
+    Write,
+}
+
+/// Counters reported by `--debug`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RateStats {
+    pub reads: u64,
+    pub writes: u64,
+    /// Requests that had to wait for a token.
+    pub throttled: u64,
+    pub waited_ms: u64,
+    /// Retries after 429/503 responses.
+    pub retries: u64,
+}
+
+/// Token-bucket limiter shared by every clone of `KalshiClient` built from the
+/// same `RuntimeConfig`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    read: Mutex<Bucket>,
+    write: Mutex<Bucket>,
+    stats: Mutex<RateStats>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    capacity: f64,
+    per_sec: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            read: Mutex::new(Bucket::new(limit.read_per_sec)),
+            write: Mutex::new(Bucket::new(limit.write_per_sec)),
+            stats: Mutex::new(RateStats::default()),
+        }
+    }
+
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Waits until a token is available in `budget`.
+    pub async fn acquire(&self, budget: Budget) {
+        let bucket = match budget {
+            Budget::Read => &self.read,
+            Budget::Write => &self.write,
+        };
+
+        let mut waited = Duration::ZERO;
+        loop {
+            let wait = match bucket.lock() {
+                Ok(mut b) => b.try_take(),
+                Err(_) => None,
+            };
+            let Some(wait) = wait else {
+                break;
+            };
+            waited += wait;
+            tokio::time::sleep(wait).await;
+        }
+
+        if let Ok(mut stats) = self.stats.lock() {
+            match budget {
+                Budget::Read => stats.reads += 1,
+                Budget::Write => stats.writes += 1,
+            }
+            if !waited.is_zero() {
+                stats.throttled += 1;
+                stats.waited_ms += waited.as_millis() as u64;
+            }
+        }
+    }
+
+    pub fn record_retry(&self) {
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.retries += 1;
+        }
+    }
+
+    pub fn stats(&self) -> RateStats {
+        self.stats.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+impl Bucket {
+    fn new(per_sec: f64) -> Self {
+        // Allow a one-second burst, but at least one request.
+        let capacity = per_sec.max(1.0);
+        Self {
+            tokens: capacity,
+            capacity,
+            per_sec,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Takes a token, or returns how long to wait before one is available.
+    /// Unlimited (rate 0) buckets never wait.
+    fn try_take(&mut self) -> Option<Duration> {
+        if self.per_sec <= 0.0 {
+            return None;
+        }
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bucket, RateLimit};
+
+    #[test]
+    fn parses_rate_limit_specs() {
+        assert_eq!("premier".parse::<RateLimit>(), Ok(RateLimit::PREMIER));
+        assert_eq!(
+            "15".parse::<RateLimit>().unwrap(),
+            RateLimit {
+                read_per_sec: 15.0,
+                write_per_sec: 15.0
+            }
+        );
+        assert_eq!(
+            "20/5".parse::<RateLimit>().unwrap(),
+            RateLimit {
+                read_per_sec: 20.0,
+                write_per_sec: 5.0
+            }
+        );
+        assert!("fast".parse::<RateLimit>().is_err());
+        assert!("-1".parse::<RateLimit>().is_err());
+        assert!("1e-300".parse::<RateLimit>().is_err());
+        assert!("0.001/0".parse::<RateLimit>().is_ok());
+    }
+
+    #[test]
+    fn bucket_waits_after_burst() {
+        let mut bucket = Bucket::new(2.0);
+        assert!(bucket.try_take().is_none());
+        assert!(bucket.try_take().is_none());
+        assert!(bucket.try_take().is_some());
+
+        let mut unlimited = Bucket::new(0.0);
+        assert!((0..100).all(|_| unlimited.try_take().is_none()));
+    }
+}
@@ -0,0 +1,553 @@
+use std::{collections::BTreeMap, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{config::search_aliases_path, error::KalshiError};
+
+/// Which part of a market or event a field comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Ticker,
+    Title,
+    Subtitle,
+}
+
+impl FieldKind {
+    fn weight(self) -> f64 {
+        match self {
+            Self::Title => 1.0,
+            Self::Subtitle => 0.7,
+            Self::Ticker => 0.6,
+        }
+    }
+}
+
+const EXACT_TICKER_SCORE: f64 = 100.0;
+const TICKER_PREFIX_SCORE: f64 = 40.0;
+const EXACT_PHRASE_SCORE: f64 = 60.0;
+const WORD_PHRASE_SCORE: f64 = 50.0;
+const SUBSTRING_PHRASE_SCORE: f64 = 30.0;
+const COVERAGE_SCORE: f64 = 40.0;
+
+/// A `markets search` query. Rows match when enough query tokens appear
+/// across their fields; matches are scored by exact ticker hits, the whole
+/// phrase appearing in a field, and token coverage (exact word, prefix,
+/// alias, substring, one edit away), weighting titles above subtitles above
+/// tickers.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    raw: String,
+    normalized: String,
+    tokens: Vec<QueryToken>,
+}
+
+#[derive(Debug, Clone)]
+struct QueryToken {
+    text: String,
+    /// Built-in and user aliases; any of them counts as a hit.
+    aliases: Vec<String>,
+    /// Ignored when deciding whether a long query matched anything
+    /// significant.
+    stop_word: bool,
+}
+
+impl SearchQuery {
+    pub fn new(query: &str, user: &SearchAliases) -> Self {
+        let normalized = normalize_for_match(query);
+        let tokens = normalized
+            .split_whitespace()
+            .map(|token| {
+                let mut aliases = builtin_aliases(token)
+                    .iter()
+                    .map(|alias| alias.to_string())
+                    .collect::<Vec<_>>();
+                for alias in user.aliases.get(token).into_iter().flatten() {
+                    if !aliases.contains(alias) {
+                        aliases.push(alias.clone());
+                    }
+                }
+                QueryToken {
+                    text: token.to_string(),
+                    aliases,
+                    stop_word: BUILTIN_STOP_WORDS.contains(&token)
+                        || user.stop_words.iter().any(|w| w == token),
+                }
+            })
+            .collect::<Vec<_>>();
+        Self {
+            raw: query.trim().to_string(),
+            normalized,
+            tokens,
+        }
+    }
+
+    /// A single word of ticker characters, e.g. `KXHIGHNY`.
+    pub fn is_ticker_like(&self) -> bool {
+        !self.raw.is_empty()
+            && self
+                .raw
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Relevance of a row made of `fields`, or `None` when it does not match.
+    /// Higher is better; scores are rounded to one decimal.
+    pub fn score(&self, fields: &[(FieldKind, Option<&str>)]) -> Option<f64> {
+        let fields = fields
+            .iter()
+            .filter_map(|(kind, text)| text.map(|t| (*kind, t, normalize_for_match(t))))
+            .filter(|(_, _, norm)| !norm.is_empty())
+            .collect::<Vec<_>>();
+        let combined = fields
+            .iter()
+            .map(|(_, _, norm)| norm.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !self.matches(&combined) {
+            return None;
+        }
+
+        let mut ticker = 0.0_f64;
+        let mut phrase = 0.0_f64;
+        let mut token_best = vec![0.0_f64; self.tokens.len()];
+        for (kind, text, norm) in &fields {
+            let weight = kind.weight();
+            if *kind == FieldKind::Ticker {
+                if text.eq_ignore_ascii_case(&self.raw) {
+                    ticker = ticker.max(EXACT_TICKER_SCORE);
+                } else if self.is_ticker_like()
+                    && text
+                        .to_ascii_uppercase()
+                        .starts_with(&self.raw.to_ascii_uppercase())
+                {
+                    ticker = ticker.max(TICKER_PREFIX_SCORE);
+                }
+            }
+
+            let field_phrase = if *norm == self.normalized {
+                EXACT_PHRASE_SCORE
+            } else if format!(" {norm} ").contains(&format!(" {} ", self.normalized)) {
+                WORD_PHRASE_SCORE
+            } else if norm.contains(&self.normalized) {
+                SUBSTRING_PHRASE_SCORE
+            } else {
+                0.0
+            };
+            phrase = phrase.max(field_phrase * weight);
+
+            let value_tokens = norm.split_whitespace().collect::<Vec<_>>();
+            for (best, token) in token_best.iter_mut().zip(&self.tokens) {
+                *best = best.max(token_score(token, &value_tokens, norm) * weight);
+            }
+        }
+
+        let coverage = if token_best.is_empty() {
+            0.0
+        } else {
+            token_best.iter().sum::<f64>() / token_best.len() as f64
+        };
+        let score = ticker + phrase + coverage * COVERAGE_SCORE;
+        Some((score * 10.0).round() / 10.0)
+    }
+
+    fn matches(&self, value_norm: &str) -> bool {
+        if value_norm.is_empty() || self.normalized.is_empty() {
+            return false;
+        }
+        if value_norm.contains(&self.normalized) {
+            return true;
+        }
+
+        let value_tokens = value_norm.split_whitespace().collect::<Vec<_>>();
+        let matched_flags = self
+            .tokens
+            .iter()
+            .map(|token| token_score(token, &value_tokens, value_norm) > 0.0)
+            .collect::<Vec<_>>();
+        let matched = matched_flags.iter().filter(|matched| **matched).count();
+
+        if self.tokens.len() >= 3 {
+            let has_significant = self.tokens.iter().any(|token| !token.stop_word);
+            let significant_matched = self
+                .tokens
+                .iter()
+                .zip(matched_flags.iter())
+                .any(|(token, matched)| !token.stop_word && *matched);
+            if has_significant && !significant_matched {
+                return false;
+            }
+        }
+
+        matched >= required_token_matches(self.tokens.len())
+    }
+}
+
+/// How well one query token is found in a field, from 0 (absent) to 1
+/// (an exact word).
+fn token_score(token: &QueryToken, value_tokens: &[&str], value_norm: &str) -> f64 {
+    let query_token = token.text.as_str();
+    // One- and two-letter tokens only ever match as words or substrings.
+    let short = query_token.len() <= 2;
+    if value_tokens.contains(&query_token) {
+        return 1.0;
+    }
+    if !short && value_tokens.iter().any(|v| v.starts_with(query_token)) {
+        return 0.85;
+    }
+
+    let alias_hit = token.aliases.iter().any(|alias| {
+        if alias.contains(' ') {
+            format!(" {value_norm} ").contains(&format!(" {alias} "))
+        } else {
+            value_tokens.iter().any(|v| v.contains(alias.as_str()))
+        }
+    });
+    if alias_hit {
+        0.8
+    } else if short {
+        if value_norm.contains(query_token) {
+            0.4
+        } else {
+            0.0
+        }
+    } else if value_tokens.iter().any(|v| v.contains(query_token)) {
+        0.6
+    } else if query_token.len() >= 5
+        && value_tokens
+            .iter()
+            .any(|v| v.len() >= 5 && edit_distance_at_most(v, query_token, 1))
+    {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+fn required_token_matches(token_count: usize) -> usize {
+    match token_count {
+        0 => 0,
+        1 => 1,
+        2 => 2,
+        3 => 3,
+        _ => token_count - 1,
+    }
+}
+
+/// Words that do not make a long query match on their own.
+const BUILTIN_STOP_WORDS: &[&str] = &[
+    "new", "york", "city", "los", "san", "las", "st", "saint", "at", "in", "on", "vs", "the",
+];
+
+const BUILTIN_ALIASES: &[(&str, &[&str])] = &[
+    (
+        "basketball",
+        &["nba", "wnba", "nbagame", "kxnbagame", "kxwnbagame"],
+    ),
+    ("football", &["nfl", "ncaaf"]),
+    ("baseball", &["mlb"]),
+    ("hockey", &["nhl"]),
+    ("soccer", &["mls", "epl", "fifa"]),
+    ("knicks", &["nyk"]),
+];
+
+fn builtin_aliases(token: &str) -> &'static [&'static str] {
+    BUILTIN_ALIASES
+        .iter()
+        .find(|(term, _)| *term == token)
+        .map_or(&[], |(_, aliases)| aliases)
+}
+
+/// User aliases and stop-words from `search-aliases.json` in the config
+/// directory, used on top of the built-in ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchAliases {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_words: Vec<String>,
+}
+
+impl SearchAliases {
+    /// The user file, or nothing when it does not exist.
+    pub fn load() -> anyhow::Result<Self> {
+        let path = search_aliases_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|err| {
+            KalshiError::config(format!(
+                "invalid search aliases in {}: {err}",
+                path.display()
+            ))
+            .into()
+        })
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = search_aliases_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Adds `aliases` for the single word `term`; returns those not already
+    /// present (built-in or user).
+    pub fn add(&mut self, term: &str, aliases: &[String]) -> anyhow::Result<Vec<String>> {
+        let term = normalize_term(term)?;
+        let mut added = Vec::new();
+        for alias in aliases {
+            let alias = normalize_for_match(alias);
+            if alias.is_empty() || alias == term {
+                anyhow::bail!(
+                    "alias `{alias}` must differ from `{term}` and contain letters or digits"
+                );
+            }
+            let known = self.aliases.get(&term).is_some_and(|a| a.contains(&alias))
+                || builtin_aliases(&term).contains(&alias.as_str());
+            if !known {
+                self.aliases
+                    .entry(term.clone())
+                    .or_default()
+                    .push(alias.clone());
+                added.push(alias);
+            }
+        }
+        Ok(added)
+    }
+
+    /// Removes the given user aliases of `term`, or all of them when
+    /// `aliases` is empty; returns the removed ones.
+    pub fn remove(&mut self, term: &str, aliases: &[String]) -> anyhow::Result<Vec<String>> {
+        let term = normalize_term(term)?;
+        let Some(existing) = self.aliases.get_mut(&term) else {
+            if builtin_aliases(&term).is_empty() {
+                anyhow::bail!("no user aliases for `{term}`");
+            }
+            anyhow::bail!("aliases of `{term}` are built in and cannot be removed");
+        };
+        let removed = if aliases.is_empty() {
+            std::mem::take(existing)
+        } else {
+            let wanted = aliases
+                .iter()
+                .map(|a| normalize_for_match(a))
+                .collect::<Vec<_>>();
+            let (removed, kept) = existing.drain(..).partition(|a| wanted.contains(a));
+            *existing = kept;
+            removed
+        };
+        if existing.is_empty() {
+            self.aliases.remove(&term);
+        }
+        Ok(removed)
+    }
+
+    /// Returns whether the word was added.
+    pub fn add_stop_word(&mut self, word: &str) -> anyhow::Result<bool> {
+        let word = normalize_term(word)?;
+        if BUILTIN_STOP_WORDS.contains(&word.as_str()) || self.stop_words.contains(&word) {
+            return Ok(false);
+        }
+        self.stop_words.push(word);
+        self.stop_words.sort();
+        Ok(true)
+    }
+
+    /// Returns whether the word was removed.
+    pub fn remove_stop_word(&mut self, word: &str) -> anyhow::Result<bool> {
+        let word = normalize_term(word)?;
+        if BUILTIN_STOP_WORDS.contains(&word.as_str()) {
+            anyhow::bail!("`{word}` is a built-in stop-word and cannot be removed");
+        }
+        let before = self.stop_words.len();
+        self.stop_words.retain(|w| *w != word);
+        Ok(self.stop_words.len() != before)
+    }
+
+    /// Every alias entry, built-in first, as `(term, aliases, built_in)`.
+    pub fn entries(&self) -> Vec<(String, Vec<String>, bool)> {
+        let mut rows = BUILTIN_ALIASES
+            .iter()
+            .map(|(term, aliases)| {
+                let aliases = aliases.iter().map(|a| a.to_string()).collect();
+                (term.to_string(), aliases, true)
+            })
+            .collect::<Vec<_>>();
+        rows.extend(
+            self.aliases
+                .iter()
+                .map(|(term, aliases)| (term.clone(), aliases.clone(), false)),
+        );
+        rows
+    }
+
+    /// Every stop-word, built-in first, as `(word, built_in)`.
+    pub fn all_stop_words(&self) -> Vec<(String, bool)> {
+        BUILTIN_STOP_WORDS
+            .iter()
+            .map(|w| (w.to_string(), true))
+            .chain(self.stop_words.iter().map(|w| (w.clone(), false)))
+            .collect()
+    }
+}
+
+/// Aliases apply per query word, so terms must normalize to one word.
+pub fn normalize_term(term: &str) -> anyhow::Result<String> {
+    let normalized = normalize_for_match(term);
+    if normalized.is_empty() || normalized.contains(' ') {
+        anyhow::bail!("`{term}` must be a single word of letters or digits");
+    }
+    Ok(normalized)
+}
+
+fn normalize_for_match(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn edit_distance_at_most(a: &str, b: &str, max: usize) -> bool {
+    let a_chars = a.chars().collect::<Vec<_>>();
+    let b_chars = b.chars().collect::<Vec<_>>();
+    let alen = a_chars.len();
+    let blen = b_chars.len();
+    if alen.abs_diff(blen) > max {
+        return false;
+    }
+
+    let mut prev = (0..=blen).collect::<Vec<_>>();
+    let mut curr = vec![0; blen + 1];
+
+    for i in 1..=alen {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=blen {
+            let cost = usize::from(a_chars[i - 1] != b_chars[j - 1]);
+            let del = prev[j] + 1;
+            let ins = curr[j - 1] + 1;
+            let sub = prev[j - 1] + cost;
+            curr[j] = del.min(ins).min(sub);
+            row_min = row_min.min(curr[j]);
+        }
+        if row_min > max {
+            return false;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[blen] <= max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldKind, SearchAliases, SearchQuery};
+
+    fn market<'a>(
+        ticker: &'a str,
+        title: &'a str,
+        subtitle: &'a str,
+    ) -> [(FieldKind, Option<&'a str>); 3] {
+        [
+            (FieldKind::Ticker, Some(ticker)),
+            (FieldKind::Title, Some(title)),
+            (FieldKind::Subtitle, Some(subtitle)),
+        ]
+    }
+
+    #[test]
+    fn ranks_exact_matches_above_loose_ones() {
+        let query = SearchQuery::new("fed rate cut", &SearchAliases::default());
+        let exact = query
+            .score(&market("KXFED-26DEC", "Fed rate cut", "Cut in December"))
+            .unwrap();
+        let loose = query
+            .score(&market(
+                "KXRATES-26",
+                "Will the federal funds rate be cut again?",
+                "Above 4%",
+            ))
+            .unwrap();
+        assert!(exact > loose, "{exact} <= {loose}");
+
+        // Title hits outweigh the same words in a subtitle, even when the
+        // subtitle is exactly the phrase.
+        let in_title = query
+            .score(&market("KXB-1", "Fed rate cut in December?", "Yes"))
+            .unwrap();
+        let in_subtitle = query
+            .score(&market("KXA-1", "Monetary policy", "Fed rate cut"))
+            .unwrap();
+        assert!(exact > in_title && in_title > in_subtitle);
+
+        assert_eq!(query.score(&market("KXNBA-1", "Lakers win?", "")), None);
+    }
+
+    #[test]
+    fn rewards_ticker_hits_and_tolerates_typos() {
+        let query = SearchQuery::new("kxhighny-26oct18-b45", &SearchAliases::default());
+        let hit = query
+            .score(&market("KXHIGHNY-26OCT18-B45", "High temp", "45° to 46°"))
+            .unwrap();
+        assert!(hit >= 100.0);
+
+        let typo = SearchQuery::new("inflaton", &SearchAliases::default());
+        assert!(typo
+            .score(&market("KXCPI-1", "Inflation above 3%?", ""))
+            .is_some());
+    }
+
+    #[test]
+    fn merges_user_aliases_and_stop_words() {
+        let mut user = SearchAliases::default();
+        assert_eq!(
+            user.add("BTC", &["Bitcoin".to_string(), "nba".to_string()])
+                .unwrap(),
+            ["bitcoin", "nba"]
+        );
+        // Already a built-in alias of `basketball`.
+        assert!(user
+            .add("basketball", &["NBA".to_string()])
+            .unwrap()
+            .is_empty());
+        assert!(user.add("two words", &["x".to_string()]).is_err());
+        assert!(user.add_stop_word("mr").unwrap());
+        assert!(!user.add_stop_word("the").unwrap());
+
+        let fields = market("KXBITCOIN-1", "Bitcoin above $100k?", "");
+        assert!(SearchQuery::new("btc", &SearchAliases::default())
+            .score(&fields)
+            .is_none());
+        assert!(SearchQuery::new("btc", &user).score(&fields).is_some());
+
+        // `mr`, `at` and `the` match but `jones` does not: enough words for a
+        // four-word query, unless `mr` is a stop-word and nothing significant
+        // matched.
+        let fields = market("KXA-1", "Mr at the office", "");
+        assert!(
+            SearchQuery::new("mr at the jones", &SearchAliases::default())
+                .score(&fields)
+                .is_some()
+        );
+        assert!(SearchQuery::new("mr at the jones", &user)
+            .score(&fields)
+            .is_none());
+
+        assert_eq!(user.remove("btc", &["NBA".to_string()]).unwrap(), ["nba"]);
+        assert_eq!(user.remove("btc", &[]).unwrap(), ["bitcoin"]);
+        assert!(user.aliases.is_empty());
+        assert!(user.remove("knicks", &[]).is_err());
+    }
+}
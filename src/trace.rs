@@ -0,0 +1,162 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+    sync::Mutex,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Headers whose values never appear in traces.
+const REDACTED_HEADERS: &[&str] = &["KALSHI-ACCESS-SIGNATURE", "KALSHI-ACCESS-KEY"];
+/// Bodies longer than this are cut in stderr traces; the NDJSON file keeps them whole.
+const STDERR_BODY_LIMIT: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum TraceLevel {
+    #[default]
+    Off,
+    /// One line per attempt: method, URL, status, latency.
+    Summary,
+    /// Summary plus redacted headers and request/response bodies.
+    Full,
+}
+
+/// One HTTP attempt, as written to stderr and the NDJSON trace file.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceRecord {
+    pub ts_ms: i64,
+    pub attempt: u32,
+    pub method: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Value>,
+    pub request_headers: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub latency_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Writes HTTP traces for `-v`, `--trace-http` and `--trace-file`.
+#[derive(Debug, Default)]
+pub struct HttpTracer {
+    level: TraceLevel,
+    file: Option<Mutex<File>>,
+}
+
+impl HttpTracer {
+    pub fn new(level: TraceLevel, file: Option<&Path>) -> anyhow::Result<Self> {
+        let file = file
+            .map(|path| {
+                let mut options = OpenOptions::new();
+                options.create(true).append(true);
+                // Traces carry request bodies; the mode only applies when the
+                // file is created, so an existing file keeps its permissions.
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::OpenOptionsExt;
+                    options.mode(0o600);
+                }
+                options.open(path).map_err(|err| {
+                    anyhow::anyhow!("cannot open trace file {}: {err}", path.display())
+                })
+            })
+            .transpose()?
+            .map(Mutex::new);
+        Ok(Self { level, file })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.level > TraceLevel::Off || self.file.is_some()
+    }
+
+    /// Whether bodies need to be captured for this tracer.
+    pub fn wants_bodies(&self) -> bool {
+        self.level == TraceLevel::Full || self.file.is_some()
+    }
+
+    pub fn record(&self, record: &TraceRecord) {
+        if self.level >= TraceLevel::Summary {
+            eprintln!("{}", summary_line(record));
+        }
+        if self.level == TraceLevel::Full {
+            for (name, value) in &record.request_headers {
+                eprintln!("http:   > {name}: {value}");
+            }
+            if let Some(body) = &record.request_body {
+                eprintln!("http:   > {body}");
+            }
+            if let Some(body) = &record.response_body {
+                eprintln!("http:   < {}", clip(body, STDERR_BODY_LIMIT));
+            }
+        }
+        if let Some(file) = &self.file {
+            if let (Ok(mut file), Ok(line)) = (file.lock(), serde_json::to_string(record)) {
+                // Tracing must never fail the request it describes.
+                let _ = writeln!(file, "{line}");
+            }
+        }
+    }
+}
+
+pub fn redact_header(name: &str, value: &str) -> String {
+    if REDACTED_HEADERS
+        .iter()
+        .any(|h| h.eq_ignore_ascii_case(name))
+    {
+        "<redacted>".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn summary_line(record: &TraceRecord) -> String {
+    let outcome = match (&record.status, &record.error) {
+        (Some(status), _) => status.to_string(),
+        (None, Some(err)) => format!("failed ({err})"),
+        (None, None) => "-".to_string(),
+    };
+    let retry = if record.attempt > 1 {
+        format!(" [attempt {}]", record.attempt)
+    } else {
+        String::new()
+    };
+    format!(
+        "http: {} {} -> {} in {}ms{retry}",
+        record.method, record.url, outcome, record.latency_ms
+    )
+}
+
+fn clip(input: &str, max_chars: usize) -> String {
+    if input.chars().count() <= max_chars {
+        return input.to_string();
+    }
+    let mut out = input.chars().take(max_chars).collect::<String>();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::redact_header;
+
+    #[test]
+    fn redacts_credentials_only() {
+        assert_eq!(
+            redact_header("KALSHI-ACCESS-SIGNATURE", "abc=="),
+            "<redacted>"
+        );
+        assert_eq!(redact_header("kalshi-access-key", "key-id"), "<redacted>");
+        assert_eq!(
+            redact_header("KALSHI-ACCESS-TIMESTAMP", "1700000000000"),
+            "1700000000000"
+        );
+    }
+}